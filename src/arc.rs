@@ -0,0 +1,149 @@
+use alloc::sync::Arc;
+use core::{
+    fmt::{Debug, Pointer},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr::NonNull,
+    sync::atomic::Ordering,
+};
+
+use crate::{AtomicNonNull, hazard};
+
+/// An atomic cell holding an [`Arc<T>`] that can be loaded while other threads replace it.
+///
+/// Loads protect the pointer with a hazard pointer in the [global](hazard::Domain::global)
+/// domain for the short window between reading it and taking a strong reference. Writers only
+/// wait for the loads that protected the value they replaced, never for loads that already saw
+/// the new one. Every operation is sequentially consistent, so no ordering is taken.
+pub struct AtomicArc<T> {
+    ptr: AtomicNonNull<T>,
+    _marker: PhantomData<Arc<T>>,
+}
+
+impl<T> Debug for AtomicArc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.ptr, f)
+    }
+}
+
+impl<T> Pointer for AtomicArc<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.ptr, f)
+    }
+}
+
+impl<T: Default> Default for AtomicArc<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<Arc<T>> for AtomicArc<T> {
+    #[inline]
+    fn from(value: Arc<T>) -> Self {
+        Self::from_arc(value)
+    }
+}
+
+impl<T> Drop for AtomicArc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: the pointer always comes from `Arc::into_raw`, and the cell owns one strong
+        // reference to it.
+        drop(unsafe { Arc::from_raw(self.ptr.get_mut().as_ptr()) });
+    }
+}
+
+impl<T> AtomicArc<T> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::from_arc(Arc::new(value))
+    }
+
+    #[inline]
+    pub fn from_arc(value: Arc<T>) -> Self {
+        Self {
+            ptr: AtomicNonNull::from_non_null(into_non_null(value)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Look at [`AtomicNonNull::into_inner`] for more information.
+    #[inline]
+    pub fn into_inner(self) -> Arc<T> {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the pointer always comes from `Arc::into_raw`, and `self` is not dropped.
+        unsafe { Arc::from_raw(this.ptr.load(Ordering::Relaxed).as_ptr()) }
+    }
+
+    /// Returns a new strong reference to the current value.
+    #[inline]
+    pub fn load(&self) -> Arc<T> {
+        let guard = hazard::protect(&self.ptr);
+        let ptr = guard.as_non_null();
+        // SAFETY: writers do not release `ptr` while the hazard protects it, so the cell still
+        // holds a strong reference to it.
+        unsafe { Arc::increment_strong_count(ptr.as_ptr()) };
+        drop(guard);
+        // SAFETY: a strong reference was taken for this `Arc` above.
+        unsafe { Arc::from_raw(ptr.as_ptr()) }
+    }
+
+    /// Stores `value`, releasing the previous one.
+    #[inline]
+    pub fn store(&self, value: Arc<T>) {
+        drop(self.swap(value));
+    }
+
+    /// Stores `value`, returning the previous one.
+    #[inline]
+    pub fn swap(&self, value: Arc<T>) -> Arc<T> {
+        let prev = self.ptr.swap(into_non_null(value), Ordering::SeqCst);
+        self.wait_for_readers(prev);
+        // SAFETY: the pointer always comes from `Arc::into_raw`, and the cell's strong reference
+        // is handed over by the swap.
+        unsafe { Arc::from_raw(prev.as_ptr()) }
+    }
+
+    /// Stores `new` if the current value is `current`, returning the previous one.
+    ///
+    /// On failure `new` is handed back together with the current address.
+    #[inline]
+    pub fn compare_and_swap(
+        &self,
+        current: &Arc<T>,
+        new: Arc<T>,
+    ) -> Result<Arc<T>, (NonNull<T>, Arc<T>)> {
+        let current = NonNull::from(current.as_ref());
+        let new = into_non_null(new);
+        match self
+            .ptr
+            .compare_exchange(current, new, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(prev) => {
+                self.wait_for_readers(prev);
+                // SAFETY: the pointer always comes from `Arc::into_raw`, and the cell's strong
+                // reference is handed over by the exchange.
+                Ok(unsafe { Arc::from_raw(prev.as_ptr()) })
+            }
+            // SAFETY: `new` was never stored, so its strong reference is still the caller's.
+            Err(prev) => Err((prev, unsafe { Arc::from_raw(new.as_ptr()) })),
+        }
+    }
+
+    /// Waits until every load that may have read the replaced pointer `prev` took its own
+    /// reference.
+    #[inline]
+    fn wait_for_readers(&self, prev: NonNull<T>) {
+        hazard::Domain::global().wait_until_unprotected(prev);
+    }
+}
+
+#[inline]
+fn into_non_null<T>(value: Arc<T>) -> NonNull<T> {
+    // SAFETY: `Arc::into_raw` never returns null.
+    unsafe { NonNull::new_unchecked(Arc::into_raw(value).cast_mut()) }
+}
@@ -0,0 +1,218 @@
+//! The atomic pointer backing [`AtomicNonNull`](crate::AtomicNonNull) and
+//! [`AtomicOptionNonNull`](crate::AtomicOptionNonNull), and the other atomics used by the crate.
+//!
+//! Under `cfg(loom)` the pointer is replaced with `loom`'s `AtomicPtr`, and with the `shuttle`
+//! feature with `shuttle`'s, so that the model checker or the randomized scheduler sees every
+//! access. The `shuttle` feature also replaces the other atomics, so that the bookkeeping of the
+//! reclamation schemes is explored as well.
+//!
+//! With the `portable-atomic` feature the atomics come from `portable_atomic` instead, which
+//! emulates the read-modify-write operations on targets lacking them, such as `thumbv6m`. A model
+//! checker's pointer still takes precedence.
+
+#[cfg(all(loom, feature = "shuttle"))]
+compile_error!("`cfg(loom)` and the `shuttle` feature cannot be enabled together");
+
+#[cfg(not(any(feature = "shuttle", feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::AtomicBool;
+#[cfg(all(
+    feature = "alloc",
+    not(loom),
+    not(any(feature = "shuttle", feature = "portable-atomic"))
+))]
+pub(crate) use core::sync::atomic::AtomicUsize;
+#[cfg(all(feature = "portable-atomic", not(feature = "shuttle")))]
+pub(crate) use portable_atomic::AtomicBool;
+#[cfg(all(
+    feature = "alloc",
+    not(loom),
+    feature = "portable-atomic",
+    not(feature = "shuttle")
+))]
+pub(crate) use portable_atomic::AtomicUsize;
+#[cfg(feature = "shuttle")]
+pub(crate) use shuttle::sync::atomic::{AtomicBool, AtomicUsize};
+
+#[cfg(not(any(loom, feature = "shuttle", feature = "portable-atomic")))]
+pub(crate) use core::sync::atomic::AtomicPtr;
+
+#[cfg(all(feature = "portable-atomic", not(any(loom, feature = "shuttle"))))]
+pub(crate) use portable_atomic::AtomicPtr;
+
+#[cfg(any(loom, feature = "shuttle"))]
+pub(crate) use self::model::AtomicPtr;
+
+#[cfg(any(loom, feature = "shuttle"))]
+mod model {
+    use core::{
+        fmt::{Debug, Pointer},
+        sync::atomic::Ordering,
+    };
+
+    #[cfg(loom)]
+    use loom::sync::atomic::AtomicPtr as Inner;
+    #[cfg(not(loom))]
+    use shuttle::sync::atomic::AtomicPtr as Inner;
+
+    /// The `AtomicPtr` of `loom` or `shuttle`, extended with the read-modify-write operations of
+    /// `core::sync::atomic::AtomicPtr` they lack, which are modelled as compare-exchange loops.
+    pub(crate) struct AtomicPtr<T>(Inner<T>);
+
+    impl<T> Debug for AtomicPtr<T> {
+        #[inline]
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl<T> Pointer for AtomicPtr<T> {
+        #[inline]
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            Pointer::fmt(&self.load(Ordering::Relaxed), f)
+        }
+    }
+
+    impl<T> AtomicPtr<T> {
+        const_fn! {
+            unless(loom);
+            #[track_caller]
+            #[inline]
+            pub(crate) const fn new(ptr: *mut T) -> Self {
+                Self(Inner::new(ptr))
+            }
+        }
+
+        #[cfg(not(loom))]
+        #[inline]
+        pub(crate) fn get_mut(&mut self) -> &mut *mut T {
+            self.0.get_mut()
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn into_inner(self) -> *mut T {
+            self.0.into_inner()
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn load(&self, order: Ordering) -> *mut T {
+            self.0.load(order)
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn store(&self, ptr: *mut T, order: Ordering) {
+            self.0.store(ptr, order);
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn swap(&self, ptr: *mut T, order: Ordering) -> *mut T {
+            self.0.swap(ptr, order)
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn compare_exchange(
+            &self,
+            current: *mut T,
+            new: *mut T,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<*mut T, *mut T> {
+            self.0.compare_exchange(current, new, success, failure)
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn compare_exchange_weak(
+            &self,
+            current: *mut T,
+            new: *mut T,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<*mut T, *mut T> {
+            self.0.compare_exchange_weak(current, new, success, failure)
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_update(
+            &self,
+            set_order: Ordering,
+            fetch_order: Ordering,
+            f: impl FnMut(*mut T) -> Option<*mut T>,
+        ) -> Result<*mut T, *mut T> {
+            self.0.fetch_update(set_order, fetch_order, f)
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_ptr_add(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.wrapping_add(val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_ptr_sub(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.wrapping_sub(val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_byte_add(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.wrapping_byte_add(val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_byte_sub(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.wrapping_byte_sub(val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_or(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.map_addr(|addr| addr | val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_and(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.map_addr(|addr| addr & val))
+        }
+
+        #[track_caller]
+        #[inline]
+        pub(crate) fn fetch_xor(&self, val: usize, order: Ordering) -> *mut T {
+            self.fetch_map(order, |ptr| ptr.map_addr(|addr| addr ^ val))
+        }
+
+        #[track_caller]
+        #[inline]
+        fn fetch_map(&self, order: Ordering, mut f: impl FnMut(*mut T) -> *mut T) -> *mut T {
+            match self
+                .0
+                .fetch_update(order, crate::load_ordering(order), |ptr| Some(f(ptr)))
+            {
+                Ok(ptr) | Err(ptr) => ptr,
+            }
+        }
+    }
+}
+
+/// Backs off in a spin loop. With the `std` feature this yields the time slice, so that a
+/// descheduled thread being waited for can run on the same core. Under a model checker it yields
+/// to the scheduler, which would otherwise keep running the spinning thread forever.
+#[inline]
+pub(crate) fn spin_loop() {
+    #[cfg(loom)]
+    loom::thread::yield_now();
+    #[cfg(feature = "shuttle")]
+    shuttle::thread::yield_now();
+    #[cfg(all(feature = "std", not(any(loom, feature = "shuttle"))))]
+    std::thread::yield_now();
+    #[cfg(not(any(feature = "std", loom, feature = "shuttle")))]
+    core::hint::spin_loop();
+}
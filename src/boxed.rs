@@ -0,0 +1,141 @@
+use alloc::boxed::Box;
+use core::{
+    fmt::{Debug, Pointer},
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr::NonNull,
+    sync::atomic::Ordering,
+};
+
+use crate::AtomicNonNull;
+
+/// An atomic cell owning a [`Box<T>`], built on [`AtomicNonNull`].
+///
+/// The pointee is dropped together with the cell.
+pub struct AtomicBox<T> {
+    ptr: AtomicNonNull<T>,
+    _marker: PhantomData<Box<T>>,
+}
+
+// SAFETY: shared access only moves boxes in and out, it never hands out `&T`.
+unsafe impl<T: Send> Sync for AtomicBox<T> {}
+
+impl<T> Debug for AtomicBox<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.ptr, f)
+    }
+}
+
+impl<T> Pointer for AtomicBox<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.ptr, f)
+    }
+}
+
+impl<T: Default> Default for AtomicBox<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<Box<T>> for AtomicBox<T> {
+    #[inline]
+    fn from(value: Box<T>) -> Self {
+        Self::from_box(value)
+    }
+}
+
+impl<T> Drop for AtomicBox<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: the pointer always comes from `Box::into_raw`, and it is owned by `self`.
+        drop(unsafe { Box::from_raw(self.ptr.get_mut().as_ptr()) });
+    }
+}
+
+impl<T> AtomicBox<T> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::from_box(Box::new(value))
+    }
+
+    #[inline]
+    pub fn from_box(value: Box<T>) -> Self {
+        Self {
+            ptr: AtomicNonNull::from_non_null(into_non_null(value)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Look at [`AtomicNonNull::into_inner`] for more information.
+    #[inline]
+    pub fn into_inner(self) -> Box<T> {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the pointer always comes from `Box::into_raw`, and `self` is not dropped.
+        unsafe { Box::from_raw(this.ptr.load(Ordering::Relaxed).as_ptr()) }
+    }
+
+    /// Look at [`AtomicNonNull::get_mut`] for more information.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        // SAFETY: the pointer always comes from `Box::into_raw`, and the mutable reference
+        // guarantees exclusive access.
+        unsafe { self.ptr.get_mut().as_mut() }
+    }
+
+    /// Returns the address of the current box, which may only be used for comparisons.
+    ///
+    /// Look at [`AtomicNonNull::load`] for more information.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> NonNull<T> {
+        self.ptr.load(order)
+    }
+
+    /// Stores `value`, dropping the previous box.
+    ///
+    /// Look at [`AtomicNonNull::store`] for more information.
+    #[inline]
+    pub fn store(&self, value: Box<T>, order: Ordering) {
+        drop(self.swap(value, order));
+    }
+
+    /// Look at [`AtomicNonNull::swap`] for more information.
+    #[inline]
+    pub fn swap(&self, value: Box<T>, order: Ordering) -> Box<T> {
+        // SAFETY: the pointer always comes from `Box::into_raw`, and ownership is handed over
+        // by the swap.
+        unsafe { Box::from_raw(self.ptr.swap(into_non_null(value), order).as_ptr()) }
+    }
+
+    /// Stores `new` if the current box is at `current`, returning the previous box.
+    ///
+    /// On failure `new` is handed back together with the current address.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange`] for more information.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: NonNull<T>,
+        new: Box<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Box<T>, (NonNull<T>, Box<T>)> {
+        let new = into_non_null(new);
+        match self.ptr.compare_exchange(current, new, success, failure) {
+            // SAFETY: the pointer always comes from `Box::into_raw`, and ownership is handed
+            // over by the exchange.
+            Ok(prev) => Ok(unsafe { Box::from_raw(prev.as_ptr()) }),
+            // SAFETY: `new` was never stored, so it is still owned by the caller.
+            Err(prev) => Err((prev, unsafe { Box::from_raw(new.as_ptr()) })),
+        }
+    }
+}
+
+#[inline]
+fn into_non_null<T>(value: Box<T>) -> NonNull<T> {
+    // SAFETY: `Box::into_raw` never returns null.
+    unsafe { NonNull::new_unchecked(Box::into_raw(value)) }
+}
@@ -0,0 +1,20 @@
+//! Lock-free collections built on [`AtomicNonNull`](crate::AtomicNonNull).
+//!
+//! The allocating collections require the `alloc` feature. They are generic over a `Reclaimer`,
+//! which defaults to a hazard-pointer `Domain` owned by the collection.
+
+#[cfg(feature = "alloc")]
+mod harris_list;
+mod mpsc_queue;
+#[cfg(feature = "alloc")]
+mod ms_queue;
+#[cfg(feature = "alloc")]
+mod treiber;
+
+#[cfg(feature = "alloc")]
+pub use harris_list::{HarrisList, Iter, Ref};
+pub use mpsc_queue::{Adapter, Link, MpscQueue};
+#[cfg(feature = "alloc")]
+pub use ms_queue::MsQueue;
+#[cfg(feature = "alloc")]
+pub use treiber::{IntoIter, PopAll, TreiberStack};
@@ -0,0 +1,442 @@
+use core::{
+    borrow::Borrow, fmt::Debug, marker::PhantomData, ops::Deref, ptr::NonNull,
+    sync::atomic::Ordering,
+};
+
+use crate::{
+    AtomicTaggedNonNull,
+    atomic::AtomicUsize,
+    hazard,
+    reclaim::{Protected, Reclaimer},
+};
+
+/// Set in a node's link once the node is logically removed.
+const MARK: usize = 1;
+
+/// A lock-free ordered set, after Harris and Michael.
+///
+/// Nodes are kept sorted between a head and a tail sentinel. A node is removed by first setting
+/// the mark bit in its own link, which freezes it, and then unlinking it from its predecessor.
+/// Traversals unlink marked nodes they come across and retire them through `R`.
+///
+/// Retired nodes still hold their values, which are dropped by whichever thread frees them at
+/// some later point, so values must be `Send + 'static`.
+pub struct HarrisList<T, R: Reclaimer = hazard::Domain> {
+    head: NonNull<Node<T>>,
+    len: AtomicUsize,
+    reclaimer: R,
+    _marker: PhantomData<T>,
+}
+
+struct Node<T> {
+    /// `None` only for the sentinels.
+    key: Option<T>,
+    /// The successor and the [`MARK`] bit. The tail's successor is dangling.
+    next: AtomicTaggedNonNull<Node<T>, 1>,
+}
+
+impl<T> Node<T> {
+    /// # Safety
+    /// The node must not be a sentinel.
+    #[inline]
+    unsafe fn key(&self) -> &T {
+        // SAFETY: the caller guarantees this is not a sentinel.
+        unsafe { self.key.as_ref().unwrap_unchecked() }
+    }
+}
+
+// SAFETY: values are moved in and dropped by whichever thread frees their node.
+unsafe impl<T: Send, R: Reclaimer + Send> Send for HarrisList<T, R> {}
+// SAFETY: values are additionally shared through `Ref`s.
+unsafe impl<T: Send + Sync, R: Reclaimer + Sync> Sync for HarrisList<T, R> {}
+
+impl<T, R: Reclaimer> Debug for HarrisList<T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HarrisList")
+            .field("len_estimate", &self.len_estimate())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Ord + Send + 'static, R: Reclaimer + Default> Default for HarrisList<T, R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, R: Reclaimer> Drop for HarrisList<T, R> {
+    fn drop(&mut self) {
+        let mut node = self.head;
+        // SAFETY: the mutable reference guarantees exclusive access, and the chain ends at the
+        // tail, the only node after the head without a key.
+        unsafe {
+            loop {
+                let next = node.as_ref().next.load(Ordering::Relaxed).0;
+                let last = node != self.head && node.as_ref().key.is_none();
+                self.reclaimer.retire(node);
+                if last {
+                    break;
+                }
+                node = next;
+            }
+        }
+    }
+}
+
+impl<T: Ord + Send + 'static, R: Reclaimer + Default> HarrisList<T, R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_reclaimer(R::default())
+    }
+}
+
+impl<T, R: Reclaimer> HarrisList<T, R> {
+    /// The reclaimer retiring removed nodes.
+    #[inline]
+    pub fn reclaimer(&self) -> &R {
+        &self.reclaimer
+    }
+
+    /// The number of values, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn len_estimate(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Ord + Send + 'static, R: Reclaimer> HarrisList<T, R> {
+    pub fn with_reclaimer(reclaimer: R) -> Self {
+        let tail = reclaimer.alloc(Node {
+            key: None,
+            next: AtomicTaggedNonNull::new(NonNull::dangling(), 0),
+        });
+        let head = reclaimer.alloc(Node {
+            key: None,
+            next: AtomicTaggedNonNull::new(tail, 0),
+        });
+        Self {
+            head,
+            len: AtomicUsize::new(0),
+            reclaimer,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Inserts `value`, returning `false` and dropping it if an equal value is already present.
+    pub fn insert(&self, value: T) -> bool {
+        let mut node = self.reclaimer.alloc(Node {
+            key: Some(value),
+            next: AtomicTaggedNonNull::new(NonNull::dangling(), 0),
+        });
+        // Count the value before linking it, so a remove of it cannot decrement first.
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let inserted = {
+            // SAFETY: the node is not shared until the exchange below succeeds, and it holds a key.
+            let (node_ref, key) = unsafe { (node.as_ref(), node.as_ref().key()) };
+            loop {
+                let pos = self.find_by(|k| k >= key);
+                let cur = pos.cur.as_non_null();
+                if pos.key() == Some(key) {
+                    break false;
+                }
+                node_ref.next.store(cur, 0, Ordering::Relaxed);
+                if pos
+                    .link()
+                    .compare_exchange((cur, 0), (node, 0), Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    break true;
+                }
+            }
+        };
+        if !inserted {
+            self.len.fetch_sub(1, Ordering::Relaxed);
+            // SAFETY: the node was never shared, so it can be retired right away, and its value
+            // is dropped here instead of whenever the reclaimer frees it.
+            unsafe {
+                drop(node.as_mut().key.take());
+                self.reclaimer.retire(node);
+            }
+        }
+        inserted
+    }
+
+    /// Removes the value equal to `key`, returning whether it was present.
+    ///
+    /// The value is dropped once the reclaimer frees its node, so [`Ref`]s to it stay valid.
+    pub fn remove<Q>(&self, key: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        loop {
+            let (pos, found) = self.find(key);
+            if !found {
+                return false;
+            }
+            let cur = pos.cur.as_non_null();
+            // SAFETY: the guard keeps `cur` alive.
+            let (next, mark) = unsafe { cur.as_ref() }
+                .next
+                .fetch_or_tag(MARK, Ordering::AcqRel);
+            if mark != 0 {
+                // Another thread removed it first; an equal value may have been inserted since.
+                continue;
+            }
+            self.len.fetch_sub(1, Ordering::Relaxed);
+            if pos
+                .link()
+                .compare_exchange((cur, 0), (next, 0), Ordering::Release, Ordering::Relaxed)
+                .is_ok()
+            {
+                // SAFETY: the exchange unlinked the marked node, which no other thread can do
+                // anymore, so it is retired exactly once.
+                unsafe { self.reclaimer.retire(cur) };
+            } else {
+                // Let a traversal unlink it.
+                drop(pos);
+                self.find(key);
+            }
+            return true;
+        }
+    }
+
+    /// Whether a value equal to `key` is present.
+    #[inline]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).1
+    }
+
+    /// Returns a reference to the value equal to `key`, which stays valid even if the value is
+    /// removed in the meantime.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<Ref<'_, T, R>>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (pos, found) = self.find(key);
+        found.then_some(Ref { guard: pos.cur })
+    }
+
+    /// Iterates over the values in ascending order.
+    ///
+    /// The iterator is weakly consistent: it yields every value present for its whole lifetime
+    /// exactly once and never yields a value twice, but may or may not yield values that are
+    /// inserted or removed concurrently.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, R> {
+        Iter {
+            list: self,
+            prev: None,
+            finished: false,
+        }
+    }
+
+    /// Whether the list is empty, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.find_by(|_| true).key().is_none()
+    }
+
+    fn find<Q>(&self, key: &Q) -> (Position<'_, T, R>, bool)
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let pos = self.find_by(|k| k.borrow() >= key);
+        let found = pos.key().is_some_and(|k| k.borrow() == key);
+        (pos, found)
+    }
+
+    /// Finds the first unmarked node for which `stop` returns `true`, or the tail, together with
+    /// its predecessor. Marked nodes on the way are unlinked and retired.
+    fn find_by(&self, mut stop: impl FnMut(&T) -> bool) -> Position<'_, T, R> {
+        'retry: loop {
+            let mut prev_guard = None;
+            let mut prev = self.head;
+            // SAFETY: the head is only retired when the list is dropped.
+            let mut cur = self
+                .reclaimer
+                .protect_with(|| unsafe { prev.as_ref() }.next.load(Ordering::Acquire).0);
+            loop {
+                let cur_ptr = cur.as_non_null();
+                // SAFETY: `prev` is either the head or kept alive by `prev_guard`.
+                let link = unsafe { &prev.as_ref().next };
+                if link.load(Ordering::Acquire) != (cur_ptr, 0) {
+                    continue 'retry;
+                }
+                // SAFETY: the unmarked `prev` still linked to `cur` after the guard took effect,
+                // so `cur` was reachable then and the guard keeps it alive.
+                let node = unsafe { cur_ptr.as_ref() };
+                let Some(key) = &node.key else {
+                    return Position {
+                        _prev_guard: prev_guard,
+                        prev,
+                        cur,
+                    };
+                };
+                let (next, mark) = node.next.load(Ordering::Acquire);
+                if mark != 0 {
+                    if link
+                        .compare_exchange(
+                            (cur_ptr, 0),
+                            (next, 0),
+                            Ordering::Release,
+                            Ordering::Relaxed,
+                        )
+                        .is_err()
+                    {
+                        continue 'retry;
+                    }
+                    // SAFETY: the exchange unlinked the marked node, which no other thread can
+                    // do anymore, so it is retired exactly once.
+                    unsafe { self.reclaimer.retire(cur_ptr) };
+                    cur = self
+                        .reclaimer
+                        .protect_with(|| link.load(Ordering::Acquire).0);
+                    continue;
+                }
+                if stop(key) {
+                    return Position {
+                        _prev_guard: prev_guard,
+                        prev,
+                        cur,
+                    };
+                }
+                let next = self
+                    .reclaimer
+                    .protect_with(|| node.next.load(Ordering::Acquire).0);
+                prev_guard = Some(cur);
+                prev = cur_ptr;
+                cur = next;
+            }
+        }
+    }
+}
+
+/// A node and its predecessor, both protected.
+struct Position<'l, T, R: Reclaimer + 'l> {
+    _prev_guard: Option<R::Guard<'l, Node<T>>>,
+    prev: NonNull<Node<T>>,
+    cur: R::Guard<'l, Node<T>>,
+}
+
+impl<T, R: Reclaimer> Position<'_, T, R> {
+    /// The link from the predecessor to the node.
+    #[inline]
+    fn link(&self) -> &AtomicTaggedNonNull<Node<T>, 1> {
+        // SAFETY: the predecessor is either the head or kept alive by its guard.
+        unsafe { &self.prev.as_ref().next }
+    }
+
+    /// The key of the node, or `None` for the tail.
+    #[inline]
+    fn key(&self) -> Option<&T> {
+        // SAFETY: the guard keeps the node alive.
+        unsafe { self.cur.as_non_null().as_ref() }.key.as_ref()
+    }
+}
+
+/// A reference to a value in a [`HarrisList`], keeping it alive while the value is removed.
+pub struct Ref<'l, T, R: Reclaimer + 'l = hazard::Domain> {
+    guard: R::Guard<'l, Node<T>>,
+}
+
+impl<T: Debug, R: Reclaimer> Debug for Ref<'_, T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T, R: Reclaimer> Deref for Ref<'_, T, R> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: the guard keeps the node alive, and sentinels are never handed out.
+        unsafe { self.guard.as_ref().key() }
+    }
+}
+
+impl<'l, T: Ord + Send + 'static, R: Reclaimer> IntoIterator for &'l HarrisList<T, R> {
+    type Item = Ref<'l, T, R>;
+    type IntoIter = Iter<'l, T, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the values of a [`HarrisList`], returned by [`HarrisList::iter`].
+pub struct Iter<'l, T, R: Reclaimer + 'l = hazard::Domain> {
+    list: &'l HarrisList<T, R>,
+    /// The last node visited, or `None` for the head.
+    prev: Option<R::Guard<'l, Node<T>>>,
+    finished: bool,
+}
+
+impl<T, R: Reclaimer> Debug for Iter<'_, T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Iter").finish_non_exhaustive()
+    }
+}
+
+impl<'l, T: Ord + Send + 'static, R: Reclaimer> Iterator for Iter<'l, T, R> {
+    type Item = Ref<'l, T, R>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let reclaimer = &self.list.reclaimer;
+        loop {
+            let prev = self
+                .prev
+                .as_ref()
+                .map_or(self.list.head, Protected::as_non_null);
+            // SAFETY: `prev` is either the head or kept alive by its guard.
+            let link = unsafe { &prev.as_ref().next };
+            let guard = reclaimer.protect_with(|| link.load(Ordering::Acquire).0);
+            let cur = if link.load(Ordering::Acquire) == (guard.as_non_null(), 0) {
+                guard
+            } else {
+                // `prev` was removed, so continue after its key from the head instead.
+                drop(guard);
+                match &self.prev {
+                    // SAFETY: only yielded nodes are kept, which are never sentinels.
+                    Some(prev) => {
+                        let last = unsafe { prev.as_ref().key() };
+                        self.list.find_by(|k| k > last).cur
+                    }
+                    None => self.list.find_by(|_| true).cur,
+                }
+            };
+            let cur_ptr = cur.as_non_null();
+            // SAFETY: `cur` was reachable after its guard took effect.
+            let node = unsafe { cur_ptr.as_ref() };
+            if node.key.is_none() {
+                self.prev = None;
+                self.finished = true;
+                return None;
+            }
+            // The node stays protected by `cur` until the second guard took effect, and if it is
+            // still unmarked afterwards, it cannot have been retired before.
+            let yielded = reclaimer.protect_with(|| cur_ptr);
+            let removed = node.next.load(Ordering::Acquire).1 != 0;
+            self.prev = Some(cur);
+            if !removed {
+                return Some(Ref { guard: yielded });
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use core::{
+    cell::UnsafeCell, fmt::Debug, marker::PhantomData, ptr::NonNull, sync::atomic::Ordering,
+};
+
+use crate::{AtomicNonNull, AtomicOptionNonNull};
+
+/// The link field a node embeds to be pushed onto an [`MpscQueue`].
+pub struct Link {
+    next: AtomicOptionNonNull<Link>,
+}
+
+impl Debug for Link {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Link").field("next", &self.next).finish()
+    }
+}
+
+impl Default for Link {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Link {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicOptionNonNull::none(),
+        }
+    }
+}
+
+/// Tells an [`MpscQueue`] which field of a node is its [`Link`].
+///
+/// # Safety
+/// `link` must return a pointer to a `Link` embedded in `node`, and `from_link` must return the
+/// node that embeds `link`.
+pub unsafe trait Adapter {
+    type Node;
+
+    fn link(node: NonNull<Self::Node>) -> NonNull<Link>;
+
+    /// # Safety
+    /// `link` must have been returned by [`Adapter::link`].
+    unsafe fn from_link(link: NonNull<Link>) -> NonNull<Self::Node>;
+}
+
+/// An intrusive multi-producer single-consumer FIFO queue, after Vyukov.
+///
+/// Nodes embed a [`Link`], so the queue never allocates. Producers only swap the head, and the
+/// consumer walks from a stub link owned by the queue for its lifetime.
+pub struct MpscQueue<'s, A: Adapter> {
+    head: AtomicNonNull<Link>,
+    tail: UnsafeCell<NonNull<Link>>,
+    stub: NonNull<Link>,
+    _marker: PhantomData<(&'s mut Link, *const A::Node)>,
+}
+
+// SAFETY: nodes are handed from producers to the consumer, so only `A::Node: Send` is needed.
+unsafe impl<A: Adapter> Send for MpscQueue<'_, A> where A::Node: Send {}
+// SAFETY: producers only touch the head atomically, and the consumer is unique by contract.
+unsafe impl<A: Adapter> Sync for MpscQueue<'_, A> where A::Node: Send {}
+
+impl<A: Adapter> Debug for MpscQueue<'_, A> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MpscQueue")
+            .field("head", &self.head)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'s, A: Adapter> MpscQueue<'s, A> {
+    /// Creates an empty queue that uses `stub` as its internal dummy link.
+    #[inline]
+    pub const fn new(stub: &'s mut Link) -> Self {
+        let stub = NonNull::from_mut(stub);
+        Self {
+            head: AtomicNonNull::from_non_null(stub),
+            tail: UnsafeCell::new(stub),
+            stub,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pushes `node`; this may be called from any number of threads.
+    ///
+    /// # Safety
+    /// `node` must stay valid and must not be pushed again until it is popped.
+    #[inline]
+    pub unsafe fn push(&self, node: NonNull<A::Node>) {
+        // SAFETY: the caller guarantees `node` is valid.
+        unsafe { self.push_link(A::link(node)) };
+    }
+
+    /// Pops the oldest node.
+    ///
+    /// Returns `None` if the queue is empty, or spuriously while a producer is between its swap
+    /// of the head and linking its node.
+    ///
+    /// # Safety
+    /// Only one thread may pop at a time.
+    pub unsafe fn pop(&self) -> Option<NonNull<A::Node>> {
+        // SAFETY: the caller guarantees this is the only consumer, so it owns `tail`, and every
+        // link between the tail and the head belongs to a node that is still valid.
+        unsafe {
+            let tail_ref = &mut *self.tail.get();
+            let mut tail = *tail_ref;
+            let mut next = tail.as_ref().next.load(Ordering::Acquire);
+            if tail == self.stub {
+                let skipped = next?;
+                *tail_ref = skipped;
+                tail = skipped;
+                next = tail.as_ref().next.load(Ordering::Acquire);
+            }
+            if let Some(next) = next {
+                *tail_ref = next;
+                return Some(A::from_link(tail));
+            }
+            if tail != self.head.load(Ordering::Acquire) {
+                return None;
+            }
+            self.push_link(self.stub);
+            let next = tail.as_ref().next.load(Ordering::Acquire)?;
+            *tail_ref = next;
+            Some(A::from_link(tail))
+        }
+    }
+
+    /// # Safety
+    /// `link` must stay valid and must not be pushed again until it is popped.
+    #[inline]
+    unsafe fn push_link(&self, link: NonNull<Link>) {
+        // SAFETY: the caller guarantees `link` is valid and not in the queue.
+        unsafe { link.as_ref().next.store(None, Ordering::Relaxed) };
+        let prev = self.head.swap(link, Ordering::AcqRel);
+        // SAFETY: `prev` stays valid until the consumer moves past it, which it cannot do before
+        // its successor is linked here.
+        unsafe { prev.as_ref().next.store(Some(link), Ordering::Release) };
+    }
+}
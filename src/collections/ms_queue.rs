@@ -0,0 +1,195 @@
+use core::{fmt::Debug, marker::PhantomData, mem::MaybeUninit, sync::atomic::Ordering};
+
+use crate::{
+    AtomicNonNull, AtomicOptionNonNull,
+    atomic::AtomicUsize,
+    hazard,
+    reclaim::{Protected, Reclaimer},
+};
+
+/// A lock-free unbounded MPMC FIFO queue, after Michael and Scott.
+///
+/// The head and tail always point to a node: the head is a dummy node whose successor holds the
+/// front value. Dequeued dummies are retired through `R`.
+pub struct MsQueue<T, R: Reclaimer = hazard::Domain> {
+    head: AtomicNonNull<Node<T>>,
+    tail: AtomicNonNull<Node<T>>,
+    len: AtomicUsize,
+    reclaimer: R,
+    _marker: PhantomData<T>,
+}
+
+struct Node<T> {
+    value: MaybeUninit<T>,
+    /// Set at most once, from `None` to the successor.
+    next: AtomicOptionNonNull<Node<T>>,
+}
+
+// SAFETY: values are moved in and out, never shared, so only `T: Send` is needed.
+unsafe impl<T: Send, R: Reclaimer + Send> Send for MsQueue<T, R> {}
+// SAFETY: as above.
+unsafe impl<T: Send, R: Reclaimer + Sync> Sync for MsQueue<T, R> {}
+
+impl<T, R: Reclaimer> Debug for MsQueue<T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MsQueue")
+            .field("len_estimate", &self.len_estimate())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, R: Reclaimer + Default> Default for MsQueue<T, R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, R: Reclaimer> Drop for MsQueue<T, R> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        // SAFETY: the mutable reference guarantees exclusive access, and every node after the
+        // dummy holds an initialized value.
+        unsafe {
+            let mut next = node.as_mut().next.get_mut().take();
+            self.reclaimer.retire(node);
+            while let Some(current) = next {
+                node = current;
+                next = node.as_mut().next.get_mut().take();
+                node.as_mut().value.assume_init_drop();
+                self.reclaimer.retire(node);
+            }
+        }
+    }
+}
+
+impl<T, R: Reclaimer + Default> MsQueue<T, R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_reclaimer(R::default())
+    }
+}
+
+impl<T, R: Reclaimer> MsQueue<T, R> {
+    pub fn with_reclaimer(reclaimer: R) -> Self {
+        let dummy = reclaimer.alloc(Node {
+            value: MaybeUninit::uninit(),
+            next: AtomicOptionNonNull::none(),
+        });
+        Self {
+            head: AtomicNonNull::from_non_null(dummy),
+            tail: AtomicNonNull::from_non_null(dummy),
+            len: AtomicUsize::new(0),
+            reclaimer,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The reclaimer retiring dequeued nodes.
+    #[inline]
+    pub fn reclaimer(&self) -> &R {
+        &self.reclaimer
+    }
+
+    pub fn push(&self, value: T) {
+        // Count the value before linking it, so the pop taking it cannot decrement first.
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let node = self.reclaimer.alloc(Node {
+            value: MaybeUninit::new(value),
+            next: AtomicOptionNonNull::none(),
+        });
+        loop {
+            let guard = self.reclaimer.protect(&self.tail);
+            let tail = guard.as_non_null();
+            // SAFETY: the guard keeps `tail` alive.
+            let next = unsafe { &tail.as_ref().next };
+            match next.load(Ordering::Acquire) {
+                Some(next) => {
+                    let _ = self.tail.compare_exchange(
+                        tail,
+                        next,
+                        Ordering::Release,
+                        Ordering::Relaxed,
+                    );
+                }
+                None => {
+                    if next
+                        .compare_exchange(None, Some(node), Ordering::Release, Ordering::Relaxed)
+                        .is_ok()
+                    {
+                        let _ = self.tail.compare_exchange(
+                            tail,
+                            node,
+                            Ordering::Release,
+                            Ordering::Relaxed,
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        loop {
+            let head_guard = self.reclaimer.protect(&self.head);
+            let head = head_guard.as_non_null();
+            let tail = self.tail.load(Ordering::Acquire);
+            // SAFETY: the guard keeps `head` alive.
+            let next = unsafe { &head.as_ref().next };
+            next.load(Ordering::Acquire)?;
+            // SAFETY: `next` was observed to be set, and it is never cleared while shared.
+            let next_guard = self
+                .reclaimer
+                .protect(unsafe { next.as_non_null_unchecked() });
+            let next = next_guard.as_non_null();
+            // While the head has not moved, its successor cannot have been dequeued, so the guard
+            // took effect before it could be retired.
+            if self.head.load(Ordering::Acquire) != head {
+                continue;
+            }
+            if head == tail {
+                // Never let the head overtake a lagging tail, which would retire a node that the
+                // tail still points to.
+                let _ =
+                    self.tail
+                        .compare_exchange(tail, next, Ordering::Release, Ordering::Relaxed);
+                continue;
+            }
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                // SAFETY: the exchange made this thread the owner of the value in `next`, which
+                // becomes the new dummy, and unlinked `head`.
+                unsafe {
+                    let value = next.as_ref().value.assume_init_read();
+                    drop(next_guard);
+                    drop(head_guard);
+                    self.reclaimer.retire(head);
+                    return Some(value);
+                }
+            }
+        }
+    }
+
+    /// Whether the queue is empty, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        let guard = self.reclaimer.protect(&self.head);
+        // SAFETY: the guard keeps the head alive.
+        unsafe { guard.as_ref() }
+            .next
+            .load(Ordering::Acquire)
+            .is_none()
+    }
+
+    /// The number of values, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn len_estimate(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,235 @@
+use core::{
+    fmt::Debug, marker::PhantomData, mem::MaybeUninit, ptr::NonNull, sync::atomic::Ordering,
+};
+
+use crate::{
+    AtomicNonNull,
+    atomic::AtomicUsize,
+    hazard,
+    reclaim::{Protected, Reclaimer},
+};
+
+/// A lock-free LIFO stack.
+///
+/// The head always points to a node; an empty stack points to a sentinel. Popped nodes are
+/// retired through `R`, which also keeps the compare-exchange on the head free of ABA.
+pub struct TreiberStack<T, R: Reclaimer = hazard::Domain> {
+    head: AtomicNonNull<Node<T>>,
+    sentinel: NonNull<Node<T>>,
+    len: AtomicUsize,
+    reclaimer: R,
+    _marker: PhantomData<T>,
+}
+
+struct Node<T> {
+    value: MaybeUninit<T>,
+    next: NonNull<Node<T>>,
+}
+
+// SAFETY: values are moved in and out, never shared, so only `T: Send` is needed.
+unsafe impl<T: Send, R: Reclaimer + Send> Send for TreiberStack<T, R> {}
+// SAFETY: as above.
+unsafe impl<T: Send, R: Reclaimer + Sync> Sync for TreiberStack<T, R> {}
+
+impl<T, R: Reclaimer> Debug for TreiberStack<T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TreiberStack")
+            .field("len_estimate", &self.len_estimate())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, R: Reclaimer + Default> Default for TreiberStack<T, R> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, R: Reclaimer> Drop for TreiberStack<T, R> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        while node != self.sentinel {
+            // SAFETY: the mutable reference guarantees exclusive access, and every node above
+            // the sentinel holds an initialized value.
+            unsafe {
+                let next = node.as_ref().next;
+                node.as_mut().value.assume_init_drop();
+                self.reclaimer.retire(node);
+                node = next;
+            }
+        }
+        // SAFETY: the sentinel is unreachable now.
+        unsafe { self.reclaimer.retire(self.sentinel) };
+    }
+}
+
+impl<T, R: Reclaimer + Default> TreiberStack<T, R> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_reclaimer(R::default())
+    }
+}
+
+impl<T, R: Reclaimer> TreiberStack<T, R> {
+    pub fn with_reclaimer(reclaimer: R) -> Self {
+        let sentinel = reclaimer.alloc(Node {
+            value: MaybeUninit::uninit(),
+            next: NonNull::dangling(),
+        });
+        Self {
+            head: AtomicNonNull::from_non_null(sentinel),
+            sentinel,
+            len: AtomicUsize::new(0),
+            reclaimer,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The reclaimer retiring popped nodes.
+    #[inline]
+    pub fn reclaimer(&self) -> &R {
+        &self.reclaimer
+    }
+
+    pub fn push(&self, value: T) {
+        // Count the value before publishing it, so the pop taking it cannot decrement first.
+        self.len.fetch_add(1, Ordering::Relaxed);
+        let mut node = self.reclaimer.alloc(Node {
+            value: MaybeUninit::new(value),
+            next: self.head.load(Ordering::Relaxed),
+        });
+        loop {
+            // SAFETY: the node is not shared until the exchange below succeeds.
+            let next = unsafe { node.as_ref().next };
+            match self
+                .head
+                .compare_exchange_weak(next, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                // SAFETY: as above.
+                Err(head) => unsafe { node.as_mut().next = head },
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        loop {
+            let guard = self.reclaimer.protect(&self.head);
+            let head = guard.as_non_null();
+            if head == self.sentinel {
+                return None;
+            }
+            // SAFETY: the guard keeps `head` alive, and `next` is never written once pushed.
+            let next = unsafe { head.as_ref().next };
+            if self
+                .head
+                .compare_exchange_weak(head, next, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                drop(guard);
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                // SAFETY: the exchange unlinked `head`, so this thread owns its value, and the
+                // node is unreachable for threads that did not protect it already.
+                unsafe {
+                    let value = head.as_ref().value.assume_init_read();
+                    self.reclaimer.retire(head);
+                    return Some(value);
+                }
+            }
+        }
+    }
+
+    /// Takes every value at once, yielding them from the most recently pushed one.
+    pub fn pop_all(&self) -> PopAll<'_, T, R> {
+        let head = self.head.swap(self.sentinel, Ordering::Acquire);
+        PopAll { stack: self, head }
+    }
+
+    /// The number of values, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn len_estimate(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Whether the stack is empty, which may be outdated by the time it is returned.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Relaxed) == self.sentinel
+    }
+}
+
+impl<T, R: Reclaimer> IntoIterator for TreiberStack<T, R> {
+    type Item = T;
+    type IntoIter = IntoIter<T, R>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { stack: self }
+    }
+}
+
+/// An owning iterator over the values of a [`TreiberStack`], from the top down.
+pub struct IntoIter<T, R: Reclaimer = hazard::Domain> {
+    stack: TreiberStack<T, R>,
+}
+
+impl<T, R: Reclaimer> Debug for IntoIter<T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.stack).finish()
+    }
+}
+
+impl<T, R: Reclaimer> Iterator for IntoIter<T, R> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.stack.pop()
+    }
+}
+
+/// An iterator over the values taken by [`TreiberStack::pop_all`].
+///
+/// Values that are not yielded are dropped together with the iterator.
+pub struct PopAll<'s, T, R: Reclaimer> {
+    stack: &'s TreiberStack<T, R>,
+    head: NonNull<Node<T>>,
+}
+
+impl<T, R: Reclaimer> Debug for PopAll<'_, T, R> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PopAll").finish_non_exhaustive()
+    }
+}
+
+impl<T, R: Reclaimer> Iterator for PopAll<'_, T, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let node = self.head;
+        if node == self.stack.sentinel {
+            return None;
+        }
+        self.stack.len.fetch_sub(1, Ordering::Relaxed);
+        // SAFETY: the swap in `pop_all` unlinked the whole chain, so this iterator owns every
+        // value in it. Other threads may still read `next` of protected nodes, so they are
+        // retired instead of freed.
+        unsafe {
+            self.head = node.as_ref().next;
+            let value = node.as_ref().value.assume_init_read();
+            self.stack.reclaimer.retire(node);
+            Some(value)
+        }
+    }
+}
+
+impl<T, R: Reclaimer> Drop for PopAll<'_, T, R> {
+    #[inline]
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
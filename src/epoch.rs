@@ -0,0 +1,296 @@
+//! Epoch-based memory reclamation.
+//!
+//! A thread [pins](pin) itself before loading pointers and unpins when the [`Guard`] is dropped.
+//! Nodes handed to [`Guard::defer_destroy`] are freed once every thread that was pinned at the
+//! time has unpinned, which the global epoch advancing twice proves.
+
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::atomic::{self, Ordering},
+};
+
+use crate::{
+    AtomicNonNull,
+    atomic::{AtomicBool, AtomicUsize},
+    reclaim::{Protected, Reclaimer},
+    registry::{Entry, Registry, RetiredList},
+};
+
+/// Set in a participant's epoch while it is pinned.
+const PINNED: usize = 1;
+
+/// How much the global epoch grows on each advance, keeping [`PINNED`] free.
+const STEP: usize = 2;
+
+/// The number of deferred nodes after which pinning threads try to advance and collect.
+const RECLAIM_THRESHOLD: usize = 64;
+
+static GLOBAL: Global = Global {
+    epoch: AtomicUsize::new(0),
+    participants: Registry::new(),
+    garbage: RetiredList::new(),
+};
+
+std::thread_local! {
+    static HANDLE: Handle = Handle::new();
+}
+
+struct Global {
+    epoch: AtomicUsize,
+    participants: Registry<Participant>,
+    garbage: RetiredList,
+}
+
+struct Participant {
+    /// The pinned epoch or-ed with [`PINNED`], or zero while unpinned.
+    epoch: AtomicUsize,
+    /// The number of live guards, only touched by the owning thread.
+    guards: AtomicUsize,
+    /// Whether the owning thread's handle is still alive.
+    owned: AtomicBool,
+}
+
+struct Handle {
+    participant: &'static Entry<Participant>,
+}
+
+impl Handle {
+    fn new() -> Self {
+        let participant = GLOBAL.participants.acquire(|| Participant {
+            epoch: AtomicUsize::new(0),
+            guards: AtomicUsize::new(0),
+            owned: AtomicBool::new(true),
+        });
+        participant.value.owned.store(true, Ordering::Relaxed);
+        Self { participant }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        let participant = &self.participant.value;
+        participant.owned.store(false, Ordering::Relaxed);
+        if participant.guards.load(Ordering::Relaxed) == 0 {
+            self.participant.release();
+        }
+    }
+}
+
+/// Pins the current thread, keeping every pointer loaded through the guard alive until it is
+/// dropped.
+///
+/// Guards may be nested; the thread stays pinned until the last one is dropped.
+///
+/// # Panics
+/// Panics if called while the thread's local storage is being destroyed.
+#[inline]
+pub fn pin() -> Guard {
+    let participant = HANDLE.with(|handle| handle.participant);
+    let guards = participant.value.guards.load(Ordering::Relaxed);
+    participant
+        .value
+        .guards
+        .store(guards + 1, Ordering::Relaxed);
+    if guards == 0 {
+        let epoch = GLOBAL.epoch.load(Ordering::Relaxed);
+        participant
+            .value
+            .epoch
+            .store(epoch | PINNED, Ordering::Relaxed);
+        atomic::fence(Ordering::SeqCst);
+    }
+    Guard {
+        participant,
+        _marker: PhantomData,
+    }
+}
+
+/// Keeps the current thread pinned for as long as it is alive.
+pub struct Guard {
+    participant: &'static Entry<Participant>,
+    _marker: PhantomData<*const ()>,
+}
+
+impl Debug for Guard {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Guard").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Guard {
+    #[inline]
+    fn drop(&mut self) {
+        let participant = &self.participant.value;
+        let guards = participant.guards.load(Ordering::Relaxed) - 1;
+        participant.guards.store(guards, Ordering::Relaxed);
+        if guards == 0 {
+            participant.epoch.store(0, Ordering::Release);
+            if !participant.owned.load(Ordering::Relaxed) {
+                self.participant.release();
+            }
+        }
+    }
+}
+
+impl Guard {
+    /// Drops `ptr` as a `Box<T>` once no thread pinned at this point is pinned anymore.
+    ///
+    /// # Safety
+    /// * `ptr` must come from `Box::into_raw` and must not be destroyed or freed otherwise.
+    /// * `ptr` must already be unreachable for threads that pin after this call.
+    /// * Dropping the `Box<T>` on any thread at a later point must be sound.
+    pub unsafe fn defer_destroy<T>(&self, ptr: NonNull<T>) {
+        atomic::fence(Ordering::SeqCst);
+        let epoch = GLOBAL.epoch.load(Ordering::Relaxed);
+        // SAFETY: the caller upholds the requirements of `RetiredList::retire`.
+        if unsafe { GLOBAL.garbage.retire(ptr, epoch, RECLAIM_THRESHOLD) } {
+            self.flush();
+        }
+    }
+
+    /// Tries to advance the global epoch and frees the nodes that became safe to free.
+    pub fn flush(&self) {
+        try_advance();
+        // SAFETY: a node deferred at epoch `e` was unreachable to threads pinning after it, and
+        // the epoch only advances twice past `e` once every thread pinned before has unpinned.
+        unsafe {
+            GLOBAL.garbage.reclaim(
+                || GLOBAL.epoch.load(Ordering::Relaxed),
+                |&epoch, node| epoch.wrapping_sub(node.stamp) >= 2 * STEP,
+            );
+        }
+    }
+}
+
+/// Advances the global epoch if every pinned participant observed the current one.
+fn try_advance() {
+    let epoch = GLOBAL.epoch.load(Ordering::Relaxed);
+    atomic::fence(Ordering::SeqCst);
+    let lagging = GLOBAL.participants.iter().any(|participant| {
+        let local = participant.value.epoch.load(Ordering::Relaxed);
+        local & PINNED != 0 && local & !PINNED != epoch
+    });
+    if !lagging {
+        atomic::fence(Ordering::Acquire);
+        let _ = GLOBAL.epoch.compare_exchange(
+            epoch,
+            epoch.wrapping_add(STEP),
+            Ordering::Release,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// The epoch-based [`Reclaimer`], pinning the current thread for every protected pointer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Epoch;
+
+// SAFETY: a protected pointer keeps the thread pinned, and deferred nodes are only freed once
+// every thread pinned before has unpinned.
+unsafe impl Reclaimer for Epoch {
+    type Guard<'r, T> = PinnedPtr<T>;
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, mut load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        let guard = pin();
+        let ptr = load();
+        PinnedPtr { guard, ptr }
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Guard::defer_destroy`.
+        unsafe { pin().defer_destroy(ptr) }
+    }
+}
+
+/// A pointer that keeps the current thread pinned, owning its [`Guard`].
+pub struct PinnedPtr<T> {
+    guard: Guard,
+    ptr: NonNull<T>,
+}
+
+impl<T> Debug for PinnedPtr<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PinnedPtr").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Protected<T> for PinnedPtr<T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
+
+impl<T> PinnedPtr<T> {
+    /// The guard keeping the thread pinned.
+    #[inline]
+    pub fn guard(&self) -> &Guard {
+        &self.guard
+    }
+}
+
+/// A pointer loaded while pinned, valid for as long as the guard it was loaded with.
+pub struct Shared<'g, T> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<(&'g Guard, *const T)>,
+}
+
+impl<T> Clone for Shared<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Shared<'_, T> {}
+
+impl<T> PartialEq for Shared<'_, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl<T> Eq for Shared<'_, T> {}
+
+impl<T> Debug for Shared<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Shared").field(&self.ptr).finish()
+    }
+}
+
+impl<'g, T> Shared<'g, T> {
+    /// The loaded pointer.
+    #[inline]
+    pub fn as_non_null(self) -> NonNull<T> {
+        self.ptr
+    }
+
+    /// # Safety
+    /// The pointer must point to a valid `T`, which holds if every pointer stored in the source
+    /// is valid until it is passed to [`Guard::defer_destroy`].
+    #[inline]
+    pub unsafe fn as_ref(self) -> &'g T {
+        // SAFETY: the caller guarantees the pointer is valid, and the guard keeps it alive.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> AtomicNonNull<T> {
+    /// Loads the pointer with [`Ordering::Acquire`], tied to the lifetime of `guard`.
+    #[inline]
+    pub fn load_guarded<'g>(&self, guard: &'g Guard) -> Shared<'g, T> {
+        let _ = guard;
+        Shared {
+            ptr: self.load(Ordering::Acquire),
+            _marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Hazard-era memory reclamation.
+//!
+//! Nodes are [allocated](Domain::alloc) with a birth era and stamped with a retire era when they
+//! are [retired](Domain::retire). A thread [protects](Domain::protect) a pointer by publishing the
+//! current era instead of the pointer itself, and a retired node is freed once no published era
+//! falls within its lifetime. Unlike epochs, a stalled thread only holds back the nodes that were
+//! alive in the era it published, which keeps the amount of garbage bounded.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+    sync::atomic::{self, Ordering},
+};
+
+use crate::{
+    AtomicNonNull,
+    atomic::AtomicUsize,
+    reclaim::{Protected, Reclaimer},
+    registry::{Entry, Registry, RetiredList},
+};
+
+/// The number of retired nodes after which a domain scans for unprotected nodes.
+const RECLAIM_THRESHOLD: usize = 64;
+
+/// The era published by a record that protects nothing.
+const NONE: usize = 0;
+
+static GLOBAL: Domain = Domain::new();
+
+/// Allocates `value` in the [global](Domain::global) domain.
+///
+/// Look at [`Domain::alloc`] for more information.
+#[inline]
+pub fn alloc<T>(value: T) -> NonNull<T> {
+    GLOBAL.alloc(value)
+}
+
+/// Protects the current pointer of `src` in the [global](Domain::global) domain.
+///
+/// Look at [`Domain::protect`] for more information.
+#[inline]
+pub fn protect<T>(src: &AtomicNonNull<T>) -> EraGuard<'static, T> {
+    GLOBAL.protect(src)
+}
+
+/// Retires `ptr` in the [global](Domain::global) domain.
+///
+/// # Safety
+/// Look at [`Domain::retire`].
+#[inline]
+pub unsafe fn retire<T>(ptr: NonNull<T>) {
+    // SAFETY: the caller upholds the requirements of `Domain::retire`.
+    unsafe { GLOBAL.retire(ptr) }
+}
+
+/// A node header holding the era the node was allocated in.
+#[repr(C)]
+struct Tracked<T> {
+    birth: usize,
+    value: T,
+}
+
+/// An era clock, the eras published against it, and the nodes retired against them.
+///
+/// Dropping a domain frees every node still retired in it.
+pub struct Domain {
+    clock: AtomicUsize,
+    eras: Registry<AtomicUsize>,
+    retired: RetiredList,
+}
+
+impl Debug for Domain {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Domain")
+            .field("clock", &self.clock)
+            .field("retired", &self.retired.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Domain {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Domain {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            clock: AtomicUsize::new(NONE + 1),
+            eras: Registry::new(),
+            retired: RetiredList::new(),
+        }
+    }
+
+    /// The domain used by [`alloc()`], [`protect`] and [`retire`].
+    #[inline]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Allocates `value` together with its birth era.
+    ///
+    /// Only pointers returned by this function may be [retired](Self::retire) in this domain.
+    pub fn alloc<T>(&self, value: T) -> NonNull<T> {
+        let tracked = Box::new(Tracked {
+            birth: self.clock.load(Ordering::Acquire),
+            value,
+        });
+        // The pointer is derived from the raw box rather than a reference to the field, so that
+        // `retire` may step back to the header and free the whole allocation through it.
+        let raw = Box::into_raw(tracked);
+        // SAFETY: `raw` comes from a box and is therefore valid and non-null.
+        unsafe { NonNull::new_unchecked(&raw mut (*raw).value) }
+    }
+
+    /// Protects the current pointer of `src` from being freed while the guard is alive.
+    ///
+    /// The pointer is re-loaded until the era clock did not move around the load, so the guard
+    /// publishes an era in which the node was reachable.
+    #[inline]
+    pub fn protect<T>(&self, src: &AtomicNonNull<T>) -> EraGuard<'_, T> {
+        self.protect_with(|| src.load(Ordering::Acquire))
+    }
+
+    /// Protects the pointer returned by `load`, calling it again until the era clock did not
+    /// move around the call.
+    ///
+    /// Look at [`Reclaimer::protect_with`] for more information.
+    pub fn protect_with<T>(&self, mut load: impl FnMut() -> NonNull<T>) -> EraGuard<'_, T> {
+        let record = self.eras.acquire(|| AtomicUsize::new(NONE));
+        let mut published = NONE;
+        loop {
+            let ptr = load();
+            let era = self.clock.load(Ordering::SeqCst);
+            if era == published {
+                return EraGuard {
+                    record,
+                    ptr,
+                    _marker: PhantomData,
+                };
+            }
+            record.value.store(era, Ordering::SeqCst);
+            // Without the fence the next acquiring pointer load could move ahead of the era
+            // store, reading a node that a concurrent scan does not see protected.
+            atomic::fence(Ordering::SeqCst);
+            published = era;
+        }
+    }
+
+    /// Retires `ptr`, dropping it once no guard of this domain published an era it was alive in.
+    ///
+    /// # Safety
+    /// * `ptr` must come from [`Domain::alloc`] of this domain and must not be retired otherwise.
+    /// * `ptr` must already be unreachable for any thread that has not protected it yet.
+    /// * Dropping the `T` on any thread at a later point must be sound.
+    pub unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller guarantees `ptr` points into a `Tracked<T>`.
+        let tracked = unsafe {
+            ptr.byte_sub(mem::offset_of!(Tracked<T>, value))
+                .cast::<Tracked<T>>()
+        };
+        let era = self.clock.fetch_add(1, Ordering::SeqCst);
+        // SAFETY: `Tracked<T>` was allocated as a box, and the caller upholds the rest.
+        if unsafe { self.retired.retire(tracked, era, RECLAIM_THRESHOLD) } {
+            self.reclaim();
+        }
+    }
+
+    /// Frees every retired node that no published era falls within the lifetime of.
+    pub fn reclaim(&self) {
+        let snapshot = || {
+            let mut eras = self
+                .eras
+                .iter()
+                .map(|record| record.value.load(Ordering::SeqCst))
+                .filter(|&era| era != NONE)
+                .collect::<Vec<_>>();
+            eras.sort_unstable();
+            eras
+        };
+        // SAFETY: a guard can only reach a node in an era between its birth and its retirement,
+        // so a node with no published era in that interval cannot be accessed anymore.
+        unsafe {
+            self.retired.reclaim(snapshot, |eras, node| {
+                // `Tracked` is `repr(C)` and starts with the birth era.
+                let birth = *node.ptr.cast::<usize>();
+                let first = eras.partition_point(|&era| era < birth);
+                eras.get(first).is_none_or(|&era| era > node.stamp)
+            });
+        }
+    }
+}
+
+// SAFETY: a retired node is only freed when no published era falls within its lifetime.
+unsafe impl Reclaimer for Domain {
+    type Guard<'r, T> = EraGuard<'r, T>;
+
+    #[inline]
+    fn alloc<T>(&self, value: T) -> NonNull<T> {
+        Domain::alloc(self, value)
+    }
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        Domain::protect_with(self, load)
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Domain::retire`.
+        unsafe { Domain::retire(self, ptr) }
+    }
+}
+
+/// A pointer protected from reclamation for as long as the guard is alive.
+pub struct EraGuard<'d, T> {
+    record: &'d Entry<AtomicUsize>,
+    ptr: NonNull<T>,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> Debug for EraGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("EraGuard").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Drop for EraGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.record.value.store(NONE, Ordering::Release);
+        self.record.release();
+    }
+}
+
+impl<T> Protected<T> for EraGuard<'_, T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
+
+impl<T> EraGuard<'_, T> {
+    /// The protected pointer.
+    #[inline]
+    pub fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+
+    /// # Safety
+    /// The protected pointer must point to a valid `T`, which holds if every pointer stored in
+    /// the source is valid until it is retired through the guard's domain.
+    #[inline]
+    pub unsafe fn as_ref(&self) -> &T {
+        // SAFETY: the caller guarantees the pointer is valid, and the era keeps it alive.
+        unsafe { self.ptr.as_ref() }
+    }
+}
@@ -0,0 +1,228 @@
+//! Hazard-pointer memory reclamation.
+//!
+//! A thread protects a pointer loaded from an [`AtomicNonNull`] by publishing it in a hazard
+//! record, and nodes unlinked from a structure are [retired](Domain::retire) instead of freed.
+//! Retired nodes are only freed once no hazard record points to them.
+
+use alloc::vec::Vec;
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    ptr::{self, NonNull},
+    sync::atomic::{self, Ordering},
+};
+
+use crate::{
+    AtomicNonNull,
+    atomic::AtomicPtr,
+    reclaim::{Protected, Reclaimer},
+    registry::{Entry, Registry, RetiredList},
+};
+
+/// The number of retired nodes after which a domain scans for unprotected nodes.
+const RECLAIM_THRESHOLD: usize = 64;
+
+static GLOBAL: Domain = Domain::new();
+
+/// Protects the current pointer of `src` in the [global](Domain::global) domain.
+///
+/// Look at [`Domain::protect`] for more information.
+#[inline]
+pub fn protect<T>(src: &AtomicNonNull<T>) -> HazardGuard<'static, T> {
+    GLOBAL.protect(src)
+}
+
+/// Retires `ptr` in the [global](Domain::global) domain.
+///
+/// # Safety
+/// Look at [`Domain::retire`].
+#[inline]
+pub unsafe fn retire<T>(ptr: NonNull<T>) {
+    // SAFETY: the caller upholds the requirements of `Domain::retire`.
+    unsafe { GLOBAL.retire(ptr) }
+}
+
+/// A set of hazard records and the nodes retired against them.
+///
+/// Dropping a domain frees every node still retired in it.
+pub struct Domain {
+    hazards: Registry<AtomicPtr<()>>,
+    retired: RetiredList,
+}
+
+impl Debug for Domain {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Domain")
+            .field("retired", &self.retired.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Domain {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Domain {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            hazards: Registry::new(),
+            retired: RetiredList::new(),
+        }
+    }
+
+    /// The domain used by [`protect`] and [`retire`].
+    #[inline]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Protects the current pointer of `src` from being freed while the guard is alive.
+    ///
+    /// The pointer is re-validated after the hazard is published, so the guard holds a value
+    /// that was stored in `src` at a point where the hazard was already visible.
+    #[inline]
+    pub fn protect<T>(&self, src: &AtomicNonNull<T>) -> HazardGuard<'_, T> {
+        self.protect_with(|| src.load(Ordering::Acquire))
+    }
+
+    /// Protects the pointer returned by `load`, calling it again after publishing the hazard
+    /// until it returns the same pointer twice.
+    ///
+    /// Look at [`Reclaimer::protect_with`] for more information.
+    pub fn protect_with<T>(&self, mut load: impl FnMut() -> NonNull<T>) -> HazardGuard<'_, T> {
+        let record = self.hazards.acquire(|| AtomicPtr::new(ptr::null_mut()));
+        let mut ptr = load();
+        loop {
+            record.value.store(ptr.as_ptr().cast(), Ordering::SeqCst);
+            // `load` may only acquire, which would let the validating load move ahead of the
+            // hazard store. The fence pairs with the one in `RetiredList::reclaim`, so either the
+            // scan sees the hazard or the validation sees the pointer unlinked.
+            atomic::fence(Ordering::SeqCst);
+            let current = load();
+            if current == ptr {
+                break;
+            }
+            ptr = current;
+        }
+        HazardGuard {
+            record,
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Retires `ptr`, dropping it as a `Box<T>` once no guard of this domain protects it.
+    ///
+    /// # Safety
+    /// * `ptr` must come from `Box::into_raw` and must not be retired or freed otherwise.
+    /// * `ptr` must already be unreachable for any thread that has not protected it yet.
+    /// * Dropping the `Box<T>` on any thread at a later point must be sound.
+    pub unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `RetiredList::retire`.
+        if unsafe { self.retired.retire(ptr, 0, RECLAIM_THRESHOLD) } {
+            self.reclaim();
+        }
+    }
+
+    /// Waits until no guard of this domain protects `ptr`.
+    ///
+    /// `ptr` must already be unreachable, so that guards taken from now on fail to validate it
+    /// and only the guards that protected it before are waited for.
+    pub(crate) fn wait_until_unprotected<T>(&self, ptr: NonNull<T>) {
+        let ptr = ptr.as_ptr().cast::<()>();
+        atomic::fence(Ordering::SeqCst);
+        for record in self.hazards.iter() {
+            while record.value.load(Ordering::SeqCst) == ptr {
+                crate::atomic::spin_loop();
+            }
+        }
+    }
+
+    /// Frees every retired node that is not currently protected.
+    pub fn reclaim(&self) {
+        let snapshot = || {
+            let mut hazards = self
+                .hazards
+                .iter()
+                .map(|record| record.value.load(Ordering::SeqCst))
+                .filter(|hazard| !hazard.is_null())
+                .collect::<Vec<_>>();
+            hazards.sort_unstable();
+            hazards
+        };
+        // SAFETY: retired nodes are unreachable, so a node that is not in the hazard snapshot
+        // taken after it was unlinked cannot be accessed anymore.
+        unsafe {
+            self.retired.reclaim(snapshot, |hazards, node| {
+                hazards.binary_search(&node.ptr).is_err()
+            });
+        }
+    }
+}
+
+// SAFETY: a retired node is only freed when no hazard record points to it.
+unsafe impl Reclaimer for Domain {
+    type Guard<'r, T> = HazardGuard<'r, T>;
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        Domain::protect_with(self, load)
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Domain::retire`.
+        unsafe { Domain::retire(self, ptr) }
+    }
+}
+
+/// A pointer protected from reclamation for as long as the guard is alive.
+pub struct HazardGuard<'d, T> {
+    record: &'d Entry<AtomicPtr<()>>,
+    ptr: NonNull<T>,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> Debug for HazardGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("HazardGuard").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Drop for HazardGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.record.value.store(ptr::null_mut(), Ordering::Release);
+        self.record.release();
+    }
+}
+
+impl<T> Protected<T> for HazardGuard<'_, T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
+
+impl<T> HazardGuard<'_, T> {
+    /// The protected pointer.
+    #[inline]
+    pub fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+
+    /// # Safety
+    /// The protected pointer must point to a valid `T`, which holds if every pointer stored in
+    /// the source is valid until it is retired through the guard's domain.
+    #[inline]
+    pub unsafe fn as_ref(&self) -> &T {
+        // SAFETY: the caller guarantees the pointer is valid, and the hazard keeps it alive.
+        unsafe { self.ptr.as_ref() }
+    }
+}
@@ -0,0 +1,166 @@
+use core::{
+    fmt::{Debug, Pointer},
+    ptr::NonNull,
+    sync::atomic::Ordering,
+};
+
+use crate::AtomicNonNull;
+
+/// An [`AtomicNonNull`] that packs a tag into the unused upper bits of the address.
+///
+/// User-space addresses on x86_64 and aarch64 Linux fit in 48 bits, leaving 16 bits for the tag.
+/// With the `la57` feature only 57-bit addresses are assumed, leaving 7 bits. The tag is masked
+/// off on load, so the returned pointer is always valid. Tags passed to any method are truncated
+/// to [`AtomicHighTaggedNonNull::TAG_BITS`] bits.
+#[repr(transparent)]
+pub struct AtomicHighTaggedNonNull<T> {
+    ptr: AtomicNonNull<T>,
+}
+
+impl<T> Debug for AtomicHighTaggedNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (ptr, tag) = self.load(Ordering::Relaxed);
+        f.debug_struct("AtomicHighTaggedNonNull")
+            .field("ptr", &ptr)
+            .field("tag", &tag)
+            .finish()
+    }
+}
+
+impl<T> Pointer for AtomicHighTaggedNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.load(Ordering::Relaxed).0, f)
+    }
+}
+
+impl<T> AtomicHighTaggedNonNull<T> {
+    /// The number of bits available for the tag.
+    pub const TAG_BITS: u32 = if cfg!(feature = "la57") { 7 } else { 16 };
+
+    /// The largest tag that can be stored.
+    pub const TAG_MASK: usize = (1 << Self::TAG_BITS) - 1;
+
+    const SHIFT: u32 = usize::BITS - Self::TAG_BITS;
+
+    /// # Panics
+    /// Panics if the address of `ptr` uses any of the tag bits.
+    #[inline]
+    pub fn new(ptr: NonNull<T>, tag: usize) -> Self {
+        Self {
+            ptr: AtomicNonNull::from_non_null(Self::compose(ptr, tag)),
+        }
+    }
+
+    /// Look at [`AtomicNonNull::into_inner`] for more information.
+    #[inline]
+    pub fn into_inner(self) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.into_inner())
+    }
+
+    /// Look at [`AtomicNonNull::load`] for more information.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.load(order))
+    }
+
+    /// Look at [`AtomicNonNull::store`] for more information.
+    ///
+    /// # Panics
+    /// Panics if the address of `ptr` uses any of the tag bits.
+    #[inline]
+    pub fn store(&self, ptr: NonNull<T>, tag: usize, order: Ordering) {
+        self.ptr.store(Self::compose(ptr, tag), order);
+    }
+
+    /// Look at [`AtomicNonNull::swap`] for more information.
+    ///
+    /// # Panics
+    /// Panics if the address of `ptr` uses any of the tag bits.
+    #[inline]
+    pub fn swap(&self, ptr: NonNull<T>, tag: usize, order: Ordering) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.swap(Self::compose(ptr, tag), order))
+    }
+
+    /// Compares the pointer and the tag together.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange`] for more information.
+    ///
+    /// # Panics
+    /// Panics if the address of either pointer uses any of the tag bits.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: (NonNull<T>, usize),
+        new: (NonNull<T>, usize),
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(NonNull<T>, usize), (NonNull<T>, usize)> {
+        self.ptr
+            .compare_exchange(
+                Self::compose(current.0, current.1),
+                Self::compose(new.0, new.1),
+                success,
+                failure,
+            )
+            .map(Self::decompose)
+            .map_err(Self::decompose)
+    }
+
+    /// Compares the pointer and the tag together.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange_weak`] for more information.
+    ///
+    /// # Panics
+    /// Panics if the address of either pointer uses any of the tag bits.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: (NonNull<T>, usize),
+        new: (NonNull<T>, usize),
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(NonNull<T>, usize), (NonNull<T>, usize)> {
+        self.ptr
+            .compare_exchange_weak(
+                Self::compose(current.0, current.1),
+                Self::compose(new.0, new.1),
+                success,
+                failure,
+            )
+            .map(Self::decompose)
+            .map_err(Self::decompose)
+    }
+
+    #[inline]
+    fn compose(ptr: NonNull<T>, tag: usize) -> NonNull<T> {
+        assert!(
+            ptr.addr().get() >> Self::SHIFT == 0,
+            "pointer address overlaps the tag bits"
+        );
+        // SAFETY: or-ing bits into a non-null address keeps it non-null.
+        unsafe {
+            NonNull::new_unchecked(
+                ptr.as_ptr()
+                    .map_addr(|addr| addr | ((tag & Self::TAG_MASK) << Self::SHIFT)),
+            )
+        }
+    }
+
+    #[inline]
+    fn decompose(ptr: NonNull<T>) -> (NonNull<T>, usize) {
+        let tag = ptr.addr().get() >> Self::SHIFT;
+        // SAFETY: only non-null pointers with clear tag bits are stored, so clearing the tag bits
+        // leaves a non-null address.
+        let ptr = unsafe {
+            NonNull::new_unchecked(
+                ptr.as_ptr()
+                    .map_addr(|addr| addr & (usize::MAX >> Self::TAG_BITS)),
+            )
+        };
+        (ptr, tag)
+    }
+}
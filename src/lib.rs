@@ -1,14 +1,94 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::{
     fmt::{Debug, Pointer},
     ptr::{self, NonNull},
-    sync::atomic::{AtomicPtr, Ordering},
+    sync::atomic::Ordering,
 };
 
+use crate::atomic::AtomicPtr;
+
+/// Declares a `const fn` that is only `const` unless the given cfg predicate holds, for the
+/// atomics of the model-checking backends that cannot be created or consumed in const contexts.
+macro_rules! const_fn {
+    (unless($cond:meta); $(#[$attr:meta])* $vis:vis const $($item:tt)*) => {
+        #[cfg(not($cond))]
+        $(#[$attr])*
+        $vis const $($item)*
+
+        #[cfg($cond)]
+        $(#[$attr])*
+        $vis $($item)*
+    };
+}
+
+#[cfg(all(feature = "alloc", not(loom), target_has_atomic = "ptr"))]
+mod arc;
+mod atomic;
+#[cfg(all(feature = "alloc", not(loom)))]
+mod boxed;
+#[cfg(not(loom))]
+pub mod collections;
+#[cfg(all(feature = "std", not(loom)))]
+pub mod epoch;
+#[cfg(all(feature = "alloc", not(loom)))]
+pub mod era;
+#[cfg(all(feature = "alloc", not(loom)))]
+pub mod hazard;
+#[cfg(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod high_tagged;
+mod option;
+mod ordered;
+pub mod ordering;
+#[cfg(all(feature = "alloc", not(loom)))]
+pub mod qsbr;
+#[cfg(all(feature = "alloc", not(loom)))]
+pub mod reclaim;
+#[cfg(all(feature = "alloc", not(loom)))]
+mod registry;
+mod tagged;
+mod versioned;
+
+#[cfg(all(feature = "alloc", not(loom), target_has_atomic = "ptr"))]
+pub use arc::AtomicArc;
+#[cfg(all(feature = "alloc", not(loom)))]
+pub use boxed::AtomicBox;
+#[cfg(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub use high_tagged::AtomicHighTaggedNonNull;
+pub use option::AtomicOptionNonNull;
+pub use ordered::AtomicOrderedNonNull;
+pub use tagged::AtomicTaggedNonNull;
+pub use versioned::AtomicVersionedNonNull;
+
 /// An atomic wrapper around [`core::ptr::NonNull`].
 ///
 /// AtomicNoneNull is marked as `repr(transparent)` for [`core::sync::atomic::AtomicPtr`].
+///
+/// Under `cfg(loom)` it wraps `loom::sync::atomic::AtomicPtr` instead, so that `loom` can
+/// model-check algorithms built on it. The methods exposing the underlying memory are then
+/// unavailable, as are the owning cells, reclamation schemes and collections of this crate.
+///
+/// The `shuttle` feature likewise wraps `shuttle::sync::atomic::AtomicPtr`, which only works
+/// inside `shuttle` tests. The methods exposing the underlying memory are unavailable as well.
+///
+/// The `portable-atomic` feature wraps `portable_atomic::AtomicPtr`, which has the same layout and
+/// API, for targets such as `thumbv6m` whose `AtomicPtr` cannot compare-exchange. There the
+/// missing operations additionally need the `critical-section` feature, or `portable-atomic`'s
+/// `unsafe-assume-single-core` cfg. `AtomicArc` is unavailable on such targets, since `alloc`
+/// lacks `Arc` there.
 #[repr(transparent)]
 pub struct AtomicNonNull<T> {
     ptr: AtomicPtr<T>,
@@ -29,30 +109,74 @@ impl<T> Pointer for AtomicNonNull<T> {
 }
 
 impl<T> AtomicNonNull<T> {
-    #[allow(clippy::not_unsafe_ptr_arg_deref)]
-    #[inline]
-    pub const fn new(ptr: *mut T) -> Option<Self> {
-        if !ptr.is_null() {
-            // SAFETY: `ptr` is not null.
-            unsafe { Some(Self::new_unchecked(ptr)) }
-        } else {
-            None
+    const_fn! {
+        unless(loom);
+        #[allow(clippy::not_unsafe_ptr_arg_deref)]
+        #[inline]
+        pub const fn new(ptr: *mut T) -> Option<Self> {
+            if !ptr.is_null() {
+                // SAFETY: `ptr` is not null.
+                unsafe { Some(Self::new_unchecked(ptr)) }
+            } else {
+                None
+            }
+        }
+    }
+
+    const_fn! {
+        unless(loom);
+        /// # Safety
+        /// `ptr` cannot be null.
+        #[inline]
+        pub const unsafe fn new_unchecked(ptr: *mut T) -> Self {
+            Self {
+                ptr: AtomicPtr::new(ptr),
+            }
+        }
+    }
+
+    const_fn! {
+        unless(loom);
+        #[inline]
+        pub const fn from_non_null(ptr: NonNull<T>) -> Self {
+            // SAFETY: `NonNull` is never null.
+            unsafe { Self::new_unchecked(ptr.as_ptr()) }
         }
     }
 
+    /// Look at [`core::sync::atomic::AtomicPtr::from_ptr`] for more information.
+    ///
     /// # Safety
-    /// `ptr` cannot be null.
+    /// * `ptr` must be aligned to `align_of::<AtomicNonNull<T>>()`.
+    /// * `ptr` must be valid for both reads and writes for the whole lifetime `'a`.
+    /// * The value behind `ptr` must not be accessed non-atomically while the returned
+    ///   reference is alive, unless those accesses are not concurrent with atomic ones.
+    #[cfg(not(any(loom, feature = "shuttle")))]
     #[inline]
-    pub const unsafe fn new_unchecked(ptr: *mut T) -> Self {
-        Self {
-            ptr: AtomicPtr::new(ptr),
-        }
+    pub const unsafe fn from_ptr<'a>(ptr: *mut NonNull<T>) -> &'a Self {
+        // SAFETY: `AtomicNonNull<T>` is transparent over `AtomicPtr<T>`, which has the same
+        // size as `NonNull<T>`, and the caller upholds the alignment and validity requirements.
+        unsafe { &*ptr.cast::<Self>() }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::from_mut`] for more information.
+    #[cfg(not(any(loom, feature = "shuttle")))]
+    #[inline]
+    pub fn from_mut(v: &mut NonNull<T>) -> &mut Self {
+        const { assert!(align_of::<AtomicPtr<T>>() == align_of::<NonNull<T>>()) };
+        // SAFETY: the alignments were checked above and the mutable reference guarantees
+        // exclusive access.
+        unsafe { &mut *(v as *mut NonNull<T>).cast::<Self>() }
     }
 
+    /// Look at [`core::sync::atomic::AtomicPtr::from_mut_slice`] for more information.
+    #[cfg(not(any(loom, feature = "shuttle")))]
     #[inline]
-    pub const fn from_non_null(ptr: NonNull<T>) -> Self {
-        // SAFETY: `NonNull` is never null.
-        unsafe { Self::new_unchecked(ptr.as_ptr()) }
+    pub fn from_mut_slice(v: &mut [NonNull<T>]) -> &mut [Self] {
+        const { assert!(align_of::<AtomicPtr<T>>() == align_of::<NonNull<T>>()) };
+        // SAFETY: the alignments were checked above and the mutable reference guarantees
+        // exclusive access.
+        unsafe { &mut *(v as *mut [NonNull<T>] as *mut [Self]) }
     }
 
     /// Look at [`core::ptr::with_exposed_provenance`] for more information.
@@ -74,6 +198,40 @@ impl<T> AtomicNonNull<T> {
         unsafe { Self::new_unchecked(ptr::dangling_mut()) }
     }
 
+    /// Look at [`core::sync::atomic::AtomicPtr::get_mut`] for more information.
+    #[cfg(not(loom))]
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut NonNull<T> {
+        // SAFETY: `self` is always non-null and `NonNull<T>` has the same layout as `*mut T`.
+        unsafe { &mut *(self.ptr.get_mut() as *mut *mut T).cast::<NonNull<T>>() }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::get_mut_slice`] for more information.
+    #[cfg(not(any(loom, feature = "shuttle")))]
+    #[inline]
+    pub fn get_mut_slice(this: &mut [Self]) -> &mut [NonNull<T>] {
+        // SAFETY: every element is non-null, `AtomicNonNull<T>` has the same layout as
+        // `NonNull<T>` and the mutable reference guarantees exclusive access.
+        unsafe { &mut *(this as *mut [Self] as *mut [NonNull<T>]) }
+    }
+
+    const_fn! {
+        unless(any(loom, feature = "shuttle"));
+        /// Look at [`core::sync::atomic::AtomicPtr::into_inner`] for more information.
+        #[inline]
+        pub const fn into_inner(self) -> NonNull<T> {
+            // SAFETY: `self` is always non-null.
+            unsafe { NonNull::new_unchecked(self.ptr.into_inner()) }
+        }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::as_ptr`] for more information.
+    #[cfg(not(any(loom, feature = "shuttle")))]
+    #[inline]
+    pub const fn as_ptr(&self) -> *mut NonNull<T> {
+        self.ptr.as_ptr().cast()
+    }
+
     /// Look at [`core::sync::atomic::AtomicPtr::load`] for more information.
     #[inline]
     pub fn load(&self, order: Ordering) -> NonNull<T> {
@@ -89,9 +247,9 @@ impl<T> AtomicNonNull<T> {
 
     /// Look at [`core::sync::atomic::AtomicPtr::swap`] for more information.
     #[inline]
-    pub fn swap(&self, other: NonNull<T>, order: Ordering) -> Self {
+    pub fn swap(&self, other: NonNull<T>, order: Ordering) -> NonNull<T> {
         // SAFETY: the old value of `self` will always be non-null.
-        unsafe { Self::new_unchecked(self.ptr.swap(other.as_ptr(), order)) }
+        unsafe { NonNull::new_unchecked(self.ptr.swap(other.as_ptr(), order)) }
     }
 
     /// Look at [`core::sync::atomic::AtomicPtr::compare_exchange`] for more information.
@@ -102,13 +260,13 @@ impl<T> AtomicNonNull<T> {
         new: NonNull<T>,
         success: Ordering,
         failure: Ordering,
-    ) -> Result<Self, Self> {
+    ) -> Result<NonNull<T>, NonNull<T>> {
         // SAFETY: `current` and `new` and `self` cannot be null.
         unsafe {
             self.ptr
                 .compare_exchange(current.as_ptr(), new.as_ptr(), success, failure)
-                .map(|ptr| Self::new_unchecked(ptr))
-                .map_err(|ptr| Self::new_unchecked(ptr))
+                .map(|ptr| NonNull::new_unchecked(ptr))
+                .map_err(|ptr| NonNull::new_unchecked(ptr))
         }
     }
 
@@ -120,13 +278,13 @@ impl<T> AtomicNonNull<T> {
         new: NonNull<T>,
         success: Ordering,
         failure: Ordering,
-    ) -> Result<Self, Self> {
+    ) -> Result<NonNull<T>, NonNull<T>> {
         // SAFETY: `current` and `new` and `self` cannot be null.
         unsafe {
             self.ptr
                 .compare_exchange_weak(current.as_ptr(), new.as_ptr(), success, failure)
-                .map(|ptr| Self::new_unchecked(ptr))
-                .map_err(|ptr| Self::new_unchecked(ptr))
+                .map(|ptr| NonNull::new_unchecked(ptr))
+                .map_err(|ptr| NonNull::new_unchecked(ptr))
         }
     }
 
@@ -137,15 +295,278 @@ impl<T> AtomicNonNull<T> {
         set_order: Ordering,
         fetch_order: Ordering,
         mut f: impl FnMut(NonNull<T>) -> Option<NonNull<T>>,
-    ) -> Result<Self, Self> {
+    ) -> Result<NonNull<T>, NonNull<T>> {
         // SAFETY: `self` and the return value of `f` must be non-null.
         unsafe {
             self.ptr
                 .fetch_update(set_order, fetch_order, |ptr| {
                     f(NonNull::new_unchecked(ptr)).map(|ptr| ptr.as_ptr())
                 })
-                .map(|ptr| Self::new_unchecked(ptr))
-                .map_err(|ptr| Self::new_unchecked(ptr))
+                .map(|ptr| NonNull::new_unchecked(ptr))
+                .map_err(|ptr| NonNull::new_unchecked(ptr))
+        }
+    }
+
+    /// Repeatedly applies `f` until the update succeeds, returning the previous pointer.
+    ///
+    /// Look at [`AtomicNonNull::fetch_update`] for more information.
+    #[inline]
+    pub fn update(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: impl FnMut(NonNull<T>) -> NonNull<T>,
+    ) -> NonNull<T> {
+        match self.fetch_update(set_order, fetch_order, |ptr| Some(f(ptr))) {
+            Ok(ptr) | Err(ptr) => ptr,
         }
     }
+
+    /// Like [`AtomicNonNull::fetch_update`], but `f` may abort the update with its own error,
+    /// which is returned together with the last observed pointer.
+    #[inline]
+    pub fn try_update<E>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: impl FnMut(NonNull<T>) -> Result<NonNull<T>, E>,
+    ) -> Result<NonNull<T>, (NonNull<T>, E)> {
+        let mut error = None;
+        self.fetch_update(set_order, fetch_order, |ptr| match f(ptr) {
+            Ok(new) => Some(new),
+            Err(err) => {
+                error = Some(err);
+                None
+            }
+        })
+        .map_err(|ptr| match error {
+            Some(err) => (ptr, err),
+            // `fetch_update` only fails after `f` rejected the update.
+            None => unreachable!(),
+        })
+    }
+
+    /// Adds `val` elements of `T` to the current address, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the wrapping result would
+    /// be null. Use [`AtomicNonNull::fetch_ptr_add_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_add`] for more information.
+    #[inline]
+    pub fn fetch_ptr_add(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().wrapping_add(val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_add`] for more information.
+    ///
+    /// # Safety
+    /// The wrapping result of the operation cannot be null.
+    #[inline]
+    pub unsafe fn fetch_ptr_add_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_ptr_add(val, order)) }
+    }
+
+    /// Subtracts `val` elements of `T` from the current address, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the wrapping result would
+    /// be null. Use [`AtomicNonNull::fetch_ptr_sub_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_sub`] for more information.
+    #[inline]
+    pub fn fetch_ptr_sub(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().wrapping_sub(val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_ptr_sub`] for more information.
+    ///
+    /// # Safety
+    /// The wrapping result of the operation cannot be null.
+    #[inline]
+    pub unsafe fn fetch_ptr_sub_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_ptr_sub(val, order)) }
+    }
+
+    /// Adds `val` bytes to the current address, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the wrapping result would
+    /// be null. Use [`AtomicNonNull::fetch_byte_add_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_add`] for more information.
+    #[inline]
+    pub fn fetch_byte_add(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().wrapping_byte_add(val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_add`] for more information.
+    ///
+    /// # Safety
+    /// The wrapping result of the operation cannot be null.
+    #[inline]
+    pub unsafe fn fetch_byte_add_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_byte_add(val, order)) }
+    }
+
+    /// Subtracts `val` bytes from the current address, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the wrapping result would
+    /// be null. Use [`AtomicNonNull::fetch_byte_sub_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_sub`] for more information.
+    #[inline]
+    pub fn fetch_byte_sub(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().wrapping_byte_sub(val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_byte_sub`] for more information.
+    ///
+    /// # Safety
+    /// The wrapping result of the operation cannot be null.
+    #[inline]
+    pub unsafe fn fetch_byte_sub_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_byte_sub(val, order)) }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_or`] for more information.
+    ///
+    /// Setting bits of a non-null address can never produce null, so this cannot fail.
+    #[inline]
+    pub fn fetch_or(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` is non-null, and or-ing bits into it keeps it non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_or(val, order)) }
+    }
+
+    /// Bitwise "and" of the current address with `val`, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the address and-ed with `val`
+    /// would be null. Use [`AtomicNonNull::fetch_and_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_and`] for more information.
+    #[inline]
+    pub fn fetch_and(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().map_addr(|addr| addr & val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_and`] for more information.
+    ///
+    /// # Safety
+    /// The address and-ed with `val` cannot be null.
+    #[inline]
+    pub unsafe fn fetch_and_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_and(val, order)) }
+    }
+
+    /// Bitwise "xor" of the current address with `val`, returning the previous pointer.
+    ///
+    /// The update is refused and `Err` holds the current pointer if the address xor-ed with `val`
+    /// would be null. Use [`AtomicNonNull::fetch_xor_unchecked`] to skip the check.
+    ///
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_xor`] for more information.
+    #[inline]
+    pub fn fetch_xor(&self, val: usize, order: Ordering) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(order, load_ordering(order), |ptr| {
+            NonNull::new(ptr.as_ptr().map_addr(|addr| addr ^ val))
+        })
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_xor`] for more information.
+    ///
+    /// # Safety
+    /// The address xor-ed with `val` cannot be null.
+    #[inline]
+    pub unsafe fn fetch_xor_unchecked(&self, val: usize, order: Ordering) -> NonNull<T> {
+        // SAFETY: the old value of `self` will always be non-null, and the caller guarantees the
+        // new value is non-null.
+        unsafe { NonNull::new_unchecked(self.ptr.fetch_xor(val, order)) }
+    }
+
+    /// Same as [`AtomicNonNull::swap`], but wraps the previous value in a new `AtomicNonNull`.
+    #[deprecated(since = "0.5.0", note = "use `swap`, which returns `NonNull<T>`")]
+    #[inline]
+    pub fn swap_atomic(&self, other: NonNull<T>, order: Ordering) -> Self {
+        Self::from_non_null(self.swap(other, order))
+    }
+
+    /// Same as [`AtomicNonNull::compare_exchange`], but wraps the result in a new `AtomicNonNull`.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `compare_exchange`, which returns `NonNull<T>`"
+    )]
+    #[inline]
+    pub fn compare_exchange_atomic(
+        &self,
+        current: NonNull<T>,
+        new: NonNull<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self> {
+        self.compare_exchange(current, new, success, failure)
+            .map(Self::from_non_null)
+            .map_err(Self::from_non_null)
+    }
+
+    /// Same as [`AtomicNonNull::compare_exchange_weak`], but wraps the result in a new
+    /// `AtomicNonNull`.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `compare_exchange_weak`, which returns `NonNull<T>`"
+    )]
+    #[inline]
+    pub fn compare_exchange_weak_atomic(
+        &self,
+        current: NonNull<T>,
+        new: NonNull<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self> {
+        self.compare_exchange_weak(current, new, success, failure)
+            .map(Self::from_non_null)
+            .map_err(Self::from_non_null)
+    }
+
+    /// Same as [`AtomicNonNull::fetch_update`], but wraps the result in a new `AtomicNonNull`.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use `fetch_update`, which returns `NonNull<T>`"
+    )]
+    #[inline]
+    pub fn fetch_update_atomic(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: impl FnMut(NonNull<T>) -> Option<NonNull<T>>,
+    ) -> Result<Self, Self> {
+        self.fetch_update(set_order, fetch_order, f)
+            .map(Self::from_non_null)
+            .map_err(Self::from_non_null)
+    }
+}
+
+/// Returns the strongest ordering usable for the load half of a read-modify-write with `order`.
+#[inline]
+const fn load_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        order => order,
+    }
 }
@@ -0,0 +1,202 @@
+use core::{
+    fmt::{Debug, Pointer},
+    ptr::{self, NonNull},
+    sync::atomic::Ordering,
+};
+
+use crate::{AtomicNonNull, atomic::AtomicPtr};
+
+/// A nullable sibling of [`AtomicNonNull`] speaking `Option<NonNull<T>>`.
+///
+/// AtomicOptionNonNull is marked as `repr(transparent)` for [`core::sync::atomic::AtomicPtr`].
+#[repr(transparent)]
+pub struct AtomicOptionNonNull<T> {
+    ptr: AtomicPtr<T>,
+}
+
+impl<T> Debug for AtomicOptionNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.ptr, f)
+    }
+}
+
+impl<T> Pointer for AtomicOptionNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.ptr, f)
+    }
+}
+
+impl<T> Default for AtomicOptionNonNull<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<T> From<AtomicNonNull<T>> for AtomicOptionNonNull<T> {
+    #[inline]
+    fn from(value: AtomicNonNull<T>) -> Self {
+        Self { ptr: value.ptr }
+    }
+}
+
+impl<T> From<Option<NonNull<T>>> for AtomicOptionNonNull<T> {
+    #[inline]
+    fn from(value: Option<NonNull<T>>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> AtomicOptionNonNull<T> {
+    const_fn! {
+        unless(loom);
+        #[inline]
+        pub const fn new(ptr: Option<NonNull<T>>) -> Self {
+            Self {
+                ptr: AtomicPtr::new(into_raw(ptr)),
+            }
+        }
+    }
+
+    const_fn! {
+        unless(loom);
+        /// Creates a new empty pointer.
+        #[inline]
+        pub const fn none() -> Self {
+            Self::new(None)
+        }
+    }
+
+    /// Returns the pointer as an [`AtomicNonNull`] if it is present.
+    #[inline]
+    pub fn into_non_null(self) -> Option<AtomicNonNull<T>> {
+        AtomicNonNull::new(self.ptr.into_inner())
+    }
+
+    /// # Safety
+    /// The pointer cannot be null.
+    #[inline]
+    pub const unsafe fn into_non_null_unchecked(self) -> AtomicNonNull<T> {
+        AtomicNonNull { ptr: self.ptr }
+    }
+
+    /// # Safety
+    /// The pointer cannot be null, and no null pointer may be stored while the returned
+    /// reference is alive.
+    #[inline]
+    pub const unsafe fn as_non_null_unchecked(&self) -> &AtomicNonNull<T> {
+        // SAFETY: both types are transparent over `AtomicPtr<T>`, and the caller guarantees the
+        // pointer stays non-null.
+        unsafe { &*(self as *const Self).cast::<AtomicNonNull<T>>() }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::get_mut`] for more information.
+    #[cfg(not(loom))]
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Option<NonNull<T>> {
+        // SAFETY: `Option<NonNull<T>>` has the same layout as `*mut T`.
+        unsafe { &mut *(self.ptr.get_mut() as *mut *mut T).cast::<Option<NonNull<T>>>() }
+    }
+
+    const_fn! {
+        unless(any(loom, feature = "shuttle"));
+        /// Look at [`core::sync::atomic::AtomicPtr::into_inner`] for more information.
+        #[inline]
+        pub const fn into_inner(self) -> Option<NonNull<T>> {
+            NonNull::new(self.ptr.into_inner())
+        }
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::as_ptr`] for more information.
+    #[cfg(not(any(loom, feature = "shuttle")))]
+    #[inline]
+    pub const fn as_ptr(&self) -> *mut Option<NonNull<T>> {
+        self.ptr.as_ptr().cast()
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::load`] for more information.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> Option<NonNull<T>> {
+        NonNull::new(self.ptr.load(order))
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::store`] for more information.
+    #[inline]
+    pub fn store(&self, value: Option<NonNull<T>>, order: Ordering) {
+        self.ptr.store(into_raw(value), order);
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::swap`] for more information.
+    #[inline]
+    pub fn swap(&self, other: Option<NonNull<T>>, order: Ordering) -> Option<NonNull<T>> {
+        NonNull::new(self.ptr.swap(into_raw(other), order))
+    }
+
+    /// Takes the pointer out, leaving `None` in its place.
+    #[inline]
+    pub fn take(&self, order: Ordering) -> Option<NonNull<T>> {
+        self.swap(None, order)
+    }
+
+    /// Replaces the pointer with `value`, returning the previous pointer.
+    #[inline]
+    pub fn replace(&self, value: NonNull<T>, order: Ordering) -> Option<NonNull<T>> {
+        self.swap(Some(value), order)
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::compare_exchange`] for more information.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: Option<NonNull<T>>,
+        new: Option<NonNull<T>>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Option<NonNull<T>>, Option<NonNull<T>>> {
+        self.ptr
+            .compare_exchange(into_raw(current), into_raw(new), success, failure)
+            .map(NonNull::new)
+            .map_err(NonNull::new)
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::compare_exchange_weak`] for more information.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: Option<NonNull<T>>,
+        new: Option<NonNull<T>>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Option<NonNull<T>>, Option<NonNull<T>>> {
+        self.ptr
+            .compare_exchange_weak(into_raw(current), into_raw(new), success, failure)
+            .map(NonNull::new)
+            .map_err(NonNull::new)
+    }
+
+    /// Look at [`core::sync::atomic::AtomicPtr::fetch_update`] for more information.
+    #[inline]
+    pub fn fetch_update(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: impl FnMut(Option<NonNull<T>>) -> Option<Option<NonNull<T>>>,
+    ) -> Result<Option<NonNull<T>>, Option<NonNull<T>>> {
+        self.ptr
+            .fetch_update(set_order, fetch_order, |ptr| {
+                f(NonNull::new(ptr)).map(into_raw)
+            })
+            .map(NonNull::new)
+            .map_err(NonNull::new)
+    }
+}
+
+#[inline]
+const fn into_raw<T>(ptr: Option<NonNull<T>>) -> *mut T {
+    match ptr {
+        Some(ptr) => ptr.as_ptr(),
+        None => ptr::null_mut(),
+    }
+}
@@ -0,0 +1,106 @@
+use core::{
+    fmt::{Debug, Pointer},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use crate::{
+    AtomicNonNull,
+    ordering::{AcquireRelease, OrderingPolicy},
+};
+
+/// An [`AtomicNonNull`] whose operations take their orderings from the policy `P`, so that call
+/// sites cannot pass the wrong one.
+///
+/// The explicit-ordering API stays available through [`AtomicOrderedNonNull::explicit`].
+#[repr(transparent)]
+pub struct AtomicOrderedNonNull<T, P: OrderingPolicy = AcquireRelease> {
+    inner: AtomicNonNull<T>,
+    _policy: PhantomData<fn() -> P>,
+}
+
+impl<T, P: OrderingPolicy> Debug for AtomicOrderedNonNull<T, P> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl<T, P: OrderingPolicy> Pointer for AtomicOrderedNonNull<T, P> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.inner, f)
+    }
+}
+
+impl<T, P: OrderingPolicy> From<AtomicNonNull<T>> for AtomicOrderedNonNull<T, P> {
+    #[inline]
+    fn from(value: AtomicNonNull<T>) -> Self {
+        Self::from_explicit(value)
+    }
+}
+
+impl<T, P: OrderingPolicy> From<NonNull<T>> for AtomicOrderedNonNull<T, P> {
+    #[inline]
+    fn from(value: NonNull<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, P: OrderingPolicy> AtomicOrderedNonNull<T, P> {
+    const_fn! {
+        unless(loom);
+        #[inline]
+        pub const fn new(ptr: NonNull<T>) -> Self {
+            Self::from_explicit(AtomicNonNull::from_non_null(ptr))
+        }
+    }
+
+    #[inline]
+    pub const fn from_explicit(inner: AtomicNonNull<T>) -> Self {
+        Self {
+            inner,
+            _policy: PhantomData,
+        }
+    }
+
+    /// The underlying [`AtomicNonNull`], for operations with orderings other than the policy's.
+    #[inline]
+    pub const fn explicit(&self) -> &AtomicNonNull<T> {
+        &self.inner
+    }
+
+    #[inline]
+    pub fn into_explicit(self) -> AtomicNonNull<T> {
+        self.inner
+    }
+
+    /// Loads the pointer with the policy's [`Load`](OrderingPolicy::Load) ordering.
+    #[inline]
+    pub fn load(&self) -> NonNull<T> {
+        self.inner.load_ordered::<P::Load>()
+    }
+
+    /// Stores `value` with the policy's [`Store`](OrderingPolicy::Store) ordering.
+    #[inline]
+    pub fn store(&self, value: NonNull<T>) {
+        self.inner.store_ordered::<P::Store>(value);
+    }
+
+    /// Swaps in `other` with the policy's [`ReadModifyWrite`](OrderingPolicy::ReadModifyWrite)
+    /// ordering, returning the previous pointer.
+    #[inline]
+    pub fn swap(&self, other: NonNull<T>) -> NonNull<T> {
+        self.inner.swap_ordered::<P::ReadModifyWrite>(other)
+    }
+
+    /// Stores `new` if the pointer is `current`, returning the previous pointer on success and
+    /// the current one on failure.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange`] for more information.
+    #[inline]
+    pub fn cas(&self, current: NonNull<T>, new: NonNull<T>) -> Result<NonNull<T>, NonNull<T>> {
+        self.inner
+            .compare_exchange_ordered::<P::ReadModifyWrite, P::Failure>(current, new)
+    }
+}
@@ -0,0 +1,227 @@
+//! Memory orderings as types, so that an ordering an operation does not accept is rejected at
+//! compile time rather than with a panic.
+//!
+//! The methods of [`AtomicNonNull`] taking an [`Ordering`] remain the dynamic path. Their
+//! `*_ordered` counterparts take the ordering as a type parameter instead, bounded by
+//! [`LoadOrdering`] for loads and compare-exchange failures, and by [`StoreOrdering`] for stores.
+//!
+//! An [`OrderingPolicy`] fixes the orderings of every operation of an
+//! [`AtomicOrderedNonNull`](crate::AtomicOrderedNonNull) up front.
+//!
+//! The orderings an operation accepts compile:
+//!
+#![cfg_attr(not(feature = "shuttle"), doc = "```")]
+// `shuttle`'s atomics only work inside a `shuttle` test.
+#![cfg_attr(feature = "shuttle", doc = "```no_run")]
+//! use core::ptr::NonNull;
+//! use atomic_non_null::{AtomicNonNull, ordering::*};
+//!
+//! let (mut a, mut b) = (0, 1);
+//! let (a, b) = (NonNull::from(&mut a), NonNull::from(&mut b));
+//! let ptr = AtomicNonNull::from_non_null(a);
+//!
+//! assert_eq!(ptr.load_ordered::<Acquire>(), a);
+//! ptr.store_ordered::<Release>(b);
+//! assert_eq!(ptr.swap_ordered::<AcqRel>(a), b);
+//! assert_eq!(ptr.compare_exchange_ordered::<AcqRel, Acquire>(a, b), Ok(a));
+//! assert_eq!(ptr.compare_exchange_ordered::<SeqCst, Relaxed>(a, b), Err(b));
+//! ```
+//!
+//! Loads reject `Release` and `AcqRel`:
+//!
+//! ```compile_fail
+//! # use core::ptr::NonNull;
+//! # use atomic_non_null::{AtomicNonNull, ordering::*};
+//! # let mut value = 0;
+//! # let ptr = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+//! ptr.load_ordered::<Release>();
+//! ```
+//!
+//! ```compile_fail
+//! # use core::ptr::NonNull;
+//! # use atomic_non_null::{AtomicNonNull, ordering::*};
+//! # let mut value = 0;
+//! # let ptr = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+//! ptr.load_ordered::<AcqRel>();
+//! ```
+//!
+//! Stores reject `Acquire` and `AcqRel`:
+//!
+//! ```compile_fail
+//! # use core::ptr::NonNull;
+//! # use atomic_non_null::{AtomicNonNull, ordering::*};
+//! # let mut value = 0;
+//! # let ptr = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+//! ptr.store_ordered::<Acquire>(NonNull::from(&mut 1));
+//! ```
+//!
+//! ```compile_fail
+//! # use core::ptr::NonNull;
+//! # use atomic_non_null::{AtomicNonNull, ordering::*};
+//! # let mut value = 0;
+//! # let ptr = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+//! ptr.store_ordered::<AcqRel>(NonNull::from(&mut 1));
+//! ```
+//!
+//! Neither does a compare-exchange accept `Release` on failure:
+//!
+//! ```compile_fail
+//! # use core::ptr::NonNull;
+//! # use atomic_non_null::{AtomicNonNull, ordering::*};
+//! # let mut value = 0;
+//! # let ptr = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+//! let current = ptr.load_ordered::<Acquire>();
+//! ptr.compare_exchange_ordered::<AcqRel, Release>(current, current);
+//! ```
+
+use core::{fmt::Debug, hash::Hash, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::AtomicNonNull;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A memory ordering known at compile time. Read-modify-write operations accept any of them.
+pub trait MemoryOrdering:
+    sealed::Sealed + Debug + Default + Clone + Copy + PartialEq + Eq + Hash + Send + Sync + 'static
+{
+    /// The dynamic [`Ordering`] this type stands for.
+    const ORDERING: Ordering;
+}
+
+/// An ordering valid for loads and for the failure case of a compare-exchange.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid ordering for loads or compare-exchange failures",
+    note = "use `Relaxed`, `Acquire` or `SeqCst`"
+)]
+pub trait LoadOrdering: MemoryOrdering {}
+
+/// An ordering valid for stores.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a valid ordering for stores",
+    note = "use `Relaxed`, `Release` or `SeqCst`"
+)]
+pub trait StoreOrdering: MemoryOrdering {}
+
+macro_rules! orderings {
+    ($($(#[$attr:meta])* $name:ident: $($bound:ident),*;)*) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl sealed::Sealed for $name {}
+
+        impl MemoryOrdering for $name {
+            const ORDERING: Ordering = Ordering::$name;
+        }
+
+        $(impl $bound for $name {})*
+
+        impl From<$name> for Ordering {
+            #[inline]
+            fn from(_: $name) -> Self {
+                Ordering::$name
+            }
+        }
+    )*};
+}
+
+orderings! {
+    /// [`Ordering::Relaxed`] as a type.
+    Relaxed: LoadOrdering, StoreOrdering;
+    /// [`Ordering::Acquire`] as a type, valid for loads and read-modify-write operations.
+    Acquire: LoadOrdering;
+    /// [`Ordering::Release`] as a type, valid for stores and read-modify-write operations.
+    Release: StoreOrdering;
+    /// [`Ordering::AcqRel`] as a type, only valid for read-modify-write operations.
+    AcqRel: ;
+    /// [`Ordering::SeqCst`] as a type.
+    SeqCst: LoadOrdering, StoreOrdering;
+}
+
+impl<T> AtomicNonNull<T> {
+    /// Like [`AtomicNonNull::load`], with the ordering checked at compile time.
+    #[inline]
+    pub fn load_ordered<O: LoadOrdering>(&self) -> NonNull<T> {
+        self.load(O::ORDERING)
+    }
+
+    /// Like [`AtomicNonNull::store`], with the ordering checked at compile time.
+    #[inline]
+    pub fn store_ordered<O: StoreOrdering>(&self, value: NonNull<T>) {
+        self.store(value, O::ORDERING);
+    }
+
+    /// Like [`AtomicNonNull::swap`], with the ordering checked at compile time.
+    #[inline]
+    pub fn swap_ordered<O: MemoryOrdering>(&self, other: NonNull<T>) -> NonNull<T> {
+        self.swap(other, O::ORDERING)
+    }
+
+    /// Like [`AtomicNonNull::compare_exchange`], with the orderings checked at compile time.
+    #[inline]
+    pub fn compare_exchange_ordered<S: MemoryOrdering, F: LoadOrdering>(
+        &self,
+        current: NonNull<T>,
+        new: NonNull<T>,
+    ) -> Result<NonNull<T>, NonNull<T>> {
+        self.compare_exchange(current, new, S::ORDERING, F::ORDERING)
+    }
+
+    /// Like [`AtomicNonNull::compare_exchange_weak`], with the orderings checked at compile
+    /// time.
+    #[inline]
+    pub fn compare_exchange_weak_ordered<S: MemoryOrdering, F: LoadOrdering>(
+        &self,
+        current: NonNull<T>,
+        new: NonNull<T>,
+    ) -> Result<NonNull<T>, NonNull<T>> {
+        self.compare_exchange_weak(current, new, S::ORDERING, F::ORDERING)
+    }
+
+    /// Like [`AtomicNonNull::fetch_update`], with the orderings checked at compile time.
+    #[inline]
+    pub fn fetch_update_ordered<S: MemoryOrdering, F: LoadOrdering>(
+        &self,
+        f: impl FnMut(NonNull<T>) -> Option<NonNull<T>>,
+    ) -> Result<NonNull<T>, NonNull<T>> {
+        self.fetch_update(S::ORDERING, F::ORDERING, f)
+    }
+}
+
+/// The orderings an [`AtomicOrderedNonNull`](crate::AtomicOrderedNonNull) uses for each kind of
+/// operation.
+pub trait OrderingPolicy {
+    /// The ordering of loads.
+    type Load: LoadOrdering;
+    /// The ordering of stores.
+    type Store: StoreOrdering;
+    /// The ordering of swaps and of successful compare-exchanges.
+    type ReadModifyWrite: MemoryOrdering;
+    /// The ordering of failed compare-exchanges.
+    type Failure: LoadOrdering;
+}
+
+/// Acquiring loads, releasing stores and read-modify-write operations that do both, which is
+/// what publishing and consuming pointers through a single cell needs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AcquireRelease;
+
+impl OrderingPolicy for AcquireRelease {
+    type Load = Acquire;
+    type Store = Release;
+    type ReadModifyWrite = AcqRel;
+    type Failure = Acquire;
+}
+
+/// Sequentially consistent operations throughout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SequentiallyConsistent;
+
+impl OrderingPolicy for SequentiallyConsistent {
+    type Load = SeqCst;
+    type Store = SeqCst;
+    type ReadModifyWrite = SeqCst;
+    type Failure = SeqCst;
+}
@@ -0,0 +1,329 @@
+//! Quiescent-state-based memory reclamation.
+//!
+//! Every participating thread [registers](Domain::register) itself and regularly announces a
+//! [quiescent state](Handle::quiescent), a point where it holds no pointer loaded from a shared
+//! structure. Retired nodes are freed once every registered thread has passed a quiescent state
+//! after they were retired, so loads need no bookkeeping at all.
+//!
+//! A [`Domain`] can also serve as the [`Reclaimer`] of a shared collection. Each protected load
+//! then keeps a participant of its own online until the guard is dropped, so threads need not
+//! be registered.
+
+use core::{
+    cell::Cell,
+    fmt::Debug,
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::atomic::{self, Ordering},
+};
+
+use crate::{
+    AtomicNonNull,
+    atomic::AtomicUsize,
+    reclaim::{Protected, Reclaimer},
+    registry::{Entry, Registry, RetiredList},
+};
+
+/// The number of retired nodes after which a domain tries to free them.
+const RECLAIM_THRESHOLD: usize = 64;
+
+/// The state of a participant that does not hold back reclamation.
+const OFFLINE: usize = usize::MAX;
+
+static GLOBAL: Domain = Domain::new();
+
+/// Registers the current thread in the [global](Domain::global) domain.
+#[inline]
+pub fn register() -> Handle<'static> {
+    GLOBAL.register()
+}
+
+/// Retires `ptr` in the [global](Domain::global) domain.
+///
+/// # Safety
+/// Look at [`Domain::retire`].
+#[inline]
+pub unsafe fn retire<T>(ptr: NonNull<T>) {
+    // SAFETY: the caller upholds the requirements of `Domain::retire`.
+    unsafe { GLOBAL.retire(ptr) }
+}
+
+/// A set of registered threads and the nodes retired against them.
+///
+/// Dropping a domain frees every node still retired in it.
+pub struct Domain {
+    period: AtomicUsize,
+    participants: Registry<AtomicUsize>,
+    retired: RetiredList,
+}
+
+impl Debug for Domain {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Domain")
+            .field("period", &self.period)
+            .field("retired", &self.retired.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Domain {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Domain {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            period: AtomicUsize::new(0),
+            participants: Registry::new(),
+            retired: RetiredList::new(),
+        }
+    }
+
+    /// The domain used by [`register`] and [`retire`].
+    #[inline]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Registers the current thread, which then holds back reclamation until it announces a
+    /// quiescent state, goes offline, or drops the handle.
+    pub fn register(&self) -> Handle<'_> {
+        let participant = self.participants.acquire(|| AtomicUsize::new(OFFLINE));
+        let handle = Handle {
+            domain: self,
+            participant,
+            guards: Cell::new(0),
+            _marker: PhantomData,
+        };
+        handle.online();
+        handle
+    }
+
+    /// Retires `ptr`, dropping it as a `Box<T>` once every registered thread has passed a
+    /// quiescent state.
+    ///
+    /// # Safety
+    /// * `ptr` must come from `Box::into_raw` and must not be retired or freed otherwise.
+    /// * `ptr` must already be unreachable from the shared structure.
+    /// * Dropping the `Box<T>` on any thread at a later point must be sound.
+    pub unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        let period = self.period.fetch_add(1, Ordering::AcqRel) + 1;
+        // SAFETY: the caller upholds the requirements of `RetiredList::retire`.
+        if unsafe { self.retired.retire(ptr, period, RECLAIM_THRESHOLD) } {
+            self.reclaim();
+        }
+    }
+
+    /// Frees every retired node that all registered threads have passed a quiescent state for.
+    pub fn reclaim(&self) {
+        let snapshot = || {
+            self.participants
+                .iter()
+                .map(|participant| participant.value.load(Ordering::Acquire))
+                .min()
+                .unwrap_or(OFFLINE)
+        };
+        // SAFETY: a participant announces a period only after it stopped using every pointer it
+        // loaded before, and a node stamped with that period was unlinked before it began.
+        unsafe {
+            self.retired
+                .reclaim(snapshot, |&seen, node| node.stamp <= seen);
+        }
+    }
+}
+
+// SAFETY: every guard keeps a participant of its own online at a period read before the pointer
+// was loaded, so nodes retired after that period are not freed until the guard is dropped, and
+// nodes retired before it were unlinked before the load.
+unsafe impl Reclaimer for Domain {
+    type Guard<'r, T> = DomainGuard<'r, T>;
+
+    /// Registers a participant for the lifetime of the guard, so that the thread does not have
+    /// to be registered or announce quiescent states itself.
+    fn protect_with<'r, T>(&'r self, mut load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        let participant = self.participants.acquire(|| AtomicUsize::new(OFFLINE));
+        let period = self.period.load(Ordering::Acquire);
+        participant.value.store(period, Ordering::SeqCst);
+        atomic::fence(Ordering::SeqCst);
+        DomainGuard {
+            participant,
+            ptr: load(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Domain::retire`.
+        unsafe { Domain::retire(self, ptr) }
+    }
+}
+
+/// A pointer loaded through the [`Reclaimer`] implementation of a [`Domain`], which holds back
+/// reclamation while the guard is alive.
+pub struct DomainGuard<'d, T> {
+    participant: &'d Entry<AtomicUsize>,
+    ptr: NonNull<T>,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> Debug for DomainGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DomainGuard").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Drop for DomainGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.participant.value.store(OFFLINE, Ordering::Release);
+        self.participant.release();
+    }
+}
+
+impl<T> Protected<T> for DomainGuard<'_, T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
+
+/// A thread's registration in a [`Domain`].
+pub struct Handle<'d> {
+    domain: &'d Domain,
+    participant: &'d Entry<AtomicUsize>,
+    /// The guards handed out through the [`Reclaimer`] implementation that are still alive.
+    guards: Cell<usize>,
+    _marker: PhantomData<*const ()>,
+}
+
+impl Debug for Handle<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Handle")
+            .field("seen", &self.participant.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Handle<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.offline();
+        self.participant.release();
+    }
+}
+
+impl Handle<'_> {
+    /// Announces that the thread holds no pointer loaded from a shared structure.
+    ///
+    /// # Panics
+    /// Panics if a guard obtained through the handle's [`Reclaimer`] implementation is alive.
+    #[inline]
+    pub fn quiescent(&self) {
+        self.assert_unguarded();
+        let period = self.domain.period.load(Ordering::Acquire);
+        self.participant.value.store(period, Ordering::Release);
+    }
+
+    /// Stops holding back reclamation, for example before blocking.
+    ///
+    /// The thread may not hold or load shared pointers until it comes back [online](Self::online),
+    /// and protecting a pointer through the handle's [`Reclaimer`] implementation panics until then.
+    ///
+    /// # Panics
+    /// Panics if a guard obtained through the handle's [`Reclaimer`] implementation is alive.
+    #[inline]
+    pub fn offline(&self) {
+        self.assert_unguarded();
+        self.participant.value.store(OFFLINE, Ordering::Release);
+    }
+
+    /// Starts holding back reclamation again after going [offline](Self::offline).
+    #[inline]
+    pub fn online(&self) {
+        self.quiescent();
+    }
+
+    /// Swaps `new` into `src` and retires the previous pointer.
+    ///
+    /// # Safety
+    /// Look at [`Domain::retire`]; every pointer stored in `src` must satisfy its requirements.
+    #[inline]
+    pub unsafe fn swap_and_retire<T>(&self, src: &AtomicNonNull<T>, new: NonNull<T>) {
+        let prev = src.swap(new, Ordering::AcqRel);
+        // SAFETY: the previous pointer was unlinked by the swap, and the caller upholds the rest.
+        unsafe { self.domain.retire(prev) };
+    }
+
+    #[inline]
+    #[track_caller]
+    fn assert_unguarded(&self) {
+        assert_eq!(self.guards.get(), 0, "a guard of the handle is still alive");
+    }
+}
+
+// SAFETY: a loaded pointer stays valid until the handle announces a quiescent state or goes
+// offline, which panics while a guard is alive, and protecting panics while it is offline.
+unsafe impl Reclaimer for Handle<'_> {
+    type Guard<'r, T>
+        = HandleGuard<'r, T>
+    where
+        Self: 'r;
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, mut load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        assert_ne!(
+            self.participant.value.load(Ordering::Relaxed),
+            OFFLINE,
+            "the handle is offline"
+        );
+        self.guards.set(self.guards.get() + 1);
+        HandleGuard {
+            guards: &self.guards,
+            ptr: load(),
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Domain::retire`.
+        unsafe { self.domain.retire(ptr) }
+    }
+}
+
+/// A pointer loaded through the [`Reclaimer`] implementation of a [`Handle`], which may not
+/// announce a quiescent state while the guard is alive.
+pub struct HandleGuard<'h, T> {
+    guards: &'h Cell<usize>,
+    ptr: NonNull<T>,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> Debug for HandleGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("HandleGuard").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Drop for HandleGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.guards.set(self.guards.get() - 1);
+    }
+}
+
+impl<T> Protected<T> for HandleGuard<'_, T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
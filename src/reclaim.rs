@@ -0,0 +1,144 @@
+//! An abstraction over memory reclamation schemes.
+//!
+//! Data structures written against [`Reclaimer`] can run with [hazard pointers](crate::hazard),
+//! [hazard eras](crate::era), [QSBR](crate::qsbr), epochs (with the `std` feature) or [`Leak`].
+
+use alloc::boxed::Box;
+use core::{fmt::Debug, marker::PhantomData, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::AtomicNonNull;
+
+/// A pointer kept alive by a [`Reclaimer`] for as long as the guard is.
+pub trait Protected<T> {
+    /// The protected pointer.
+    fn as_non_null(&self) -> NonNull<T>;
+
+    /// # Safety
+    /// The protected pointer must point to a valid `T`, which holds if every pointer stored in
+    /// the source was allocated by the reclaimer and stays valid until it is retired.
+    #[inline]
+    unsafe fn as_ref(&self) -> &T {
+        // SAFETY: the caller guarantees the pointer is valid, and the guard keeps it alive.
+        unsafe { self.as_non_null().as_ref() }
+    }
+}
+
+/// A memory reclamation scheme.
+///
+/// # Safety
+/// If a pointer returned by [`Reclaimer::protect_with`] was still reachable from the shared
+/// structure when `load` last returned it, no [retired](Reclaimer::retire) pointer equal to it may
+/// be freed, by this reclaimer or any other sharing its state, until the guard is dropped. This
+/// must hold for any sequence of safe calls on the reclaimer and its guards.
+pub unsafe trait Reclaimer {
+    /// The guard returned by [`Reclaimer::protect`].
+    type Guard<'r, T>: Protected<T>
+    where
+        Self: 'r;
+
+    /// Allocates `value` so that it can later be retired.
+    #[inline]
+    fn alloc<T>(&self, value: T) -> NonNull<T> {
+        NonNull::from(Box::leak(Box::new(value)))
+    }
+
+    /// Loads the current pointer of `src` and protects it from being freed.
+    #[inline]
+    fn protect<'r, T>(&'r self, src: &AtomicNonNull<T>) -> Self::Guard<'r, T> {
+        self.protect_with(|| src.load(Ordering::Acquire))
+    }
+
+    /// Protects the pointer returned by `load`, which may be called several times until the
+    /// protection is known to have taken effect before the pointer could be retired.
+    ///
+    /// `load` should read the pointer from shared memory with at least [`Ordering::Acquire`],
+    /// for example from a tagged link with the tag masked off. The pointer is only protected if
+    /// it was still reachable when `load` last returned it, which callers traversing nodes that
+    /// may be unlinked have to validate themselves.
+    fn protect_with<'r, T>(&'r self, load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T>;
+
+    /// Frees `ptr` once no guard protects it anymore.
+    ///
+    /// # Safety
+    /// * `ptr` must come from [`Reclaimer::alloc`] of this reclaimer and must not be retired
+    ///   otherwise.
+    /// * `ptr` must already be unreachable for any thread that has not protected it yet.
+    /// * Dropping the `T` on any thread at a later point must be sound.
+    unsafe fn retire<T>(&self, ptr: NonNull<T>);
+}
+
+// SAFETY: forwards to the referenced reclaimer.
+unsafe impl<R: Reclaimer + ?Sized> Reclaimer for &R {
+    type Guard<'r, T>
+        = R::Guard<'r, T>
+    where
+        Self: 'r;
+
+    #[inline]
+    fn alloc<T>(&self, value: T) -> NonNull<T> {
+        R::alloc(self, value)
+    }
+
+    #[inline]
+    fn protect<'r, T>(&'r self, src: &AtomicNonNull<T>) -> Self::Guard<'r, T> {
+        R::protect(self, src)
+    }
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        R::protect_with(self, load)
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, ptr: NonNull<T>) {
+        // SAFETY: the caller upholds the requirements of `Reclaimer::retire`.
+        unsafe { R::retire(self, ptr) }
+    }
+}
+
+/// A pointer loaded without any bookkeeping, for reclaimers whose loads are free.
+pub struct Loaded<'r, T> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<(&'r (), *const T)>,
+}
+
+impl<T> Debug for Loaded<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Loaded").field(&self.ptr).finish()
+    }
+}
+
+impl<T> Protected<T> for Loaded<'_, T> {
+    #[inline]
+    fn as_non_null(&self) -> NonNull<T> {
+        self.ptr
+    }
+}
+
+impl<T> Loaded<'_, T> {
+    #[inline]
+    pub(crate) fn new(ptr: NonNull<T>) -> Self {
+        Self {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A reclaimer that never frees retired nodes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Leak;
+
+// SAFETY: retired nodes are never freed.
+unsafe impl Reclaimer for Leak {
+    type Guard<'r, T> = Loaded<'r, T>;
+
+    #[inline]
+    fn protect_with<'r, T>(&'r self, mut load: impl FnMut() -> NonNull<T>) -> Self::Guard<'r, T> {
+        Loaded::new(load())
+    }
+
+    #[inline]
+    unsafe fn retire<T>(&self, _ptr: NonNull<T>) {}
+}
@@ -0,0 +1,246 @@
+use alloc::boxed::Box;
+use core::{
+    ptr::NonNull,
+    sync::atomic::{self, Ordering},
+};
+
+use crate::{
+    AtomicOptionNonNull,
+    atomic::{AtomicBool, AtomicUsize},
+};
+
+/// A grow-only list of per-thread records, which are reused once released.
+///
+/// Records are only freed when the registry is dropped, so references to them stay valid for
+/// the registry's lifetime.
+pub(crate) struct Registry<R> {
+    head: AtomicOptionNonNull<Entry<R>>,
+}
+
+pub(crate) struct Entry<R> {
+    pub(crate) value: R,
+    active: AtomicBool,
+    next: Option<NonNull<Entry<R>>>,
+}
+
+// SAFETY: `next` is never written once the entry is shared.
+unsafe impl<R: Send> Send for Entry<R> {}
+// SAFETY: `next` is never written once the entry is shared.
+unsafe impl<R: Sync> Sync for Entry<R> {}
+
+impl<R> Drop for Registry<R> {
+    fn drop(&mut self) {
+        let mut entries = self.head.take(Ordering::Relaxed);
+        while let Some(entry) = entries {
+            // SAFETY: the mutable reference guarantees no entry is borrowed anymore.
+            entries = unsafe { Box::from_raw(entry.as_ptr()) }.next;
+        }
+    }
+}
+
+impl<R> Registry<R> {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: AtomicOptionNonNull::none(),
+        }
+    }
+
+    /// Claims a released entry, or allocates a new one with `init`.
+    pub(crate) fn acquire(&self, init: impl FnOnce() -> R) -> &Entry<R> {
+        if let Some(entry) = self.iter().find(|entry| {
+            !entry.active.load(Ordering::Relaxed)
+                && entry
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+        }) {
+            return entry;
+        }
+
+        let mut entry = NonNull::from(Box::leak(Box::new(Entry {
+            value: init(),
+            active: AtomicBool::new(true),
+            next: None,
+        })));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the entry is not shared until the exchange below succeeds.
+            unsafe { entry.as_mut().next = head };
+            match self.head.compare_exchange_weak(
+                head,
+                Some(entry),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                // SAFETY: entries are only freed when the registry is dropped.
+                Ok(_) => return unsafe { entry.as_ref() },
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Iterates over every entry, released or not.
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Entry<R>> {
+        let mut entries = self.head.load(Ordering::Acquire);
+        core::iter::from_fn(move || {
+            // SAFETY: entries are only freed when the registry is dropped.
+            let entry = unsafe { entries?.as_ref() };
+            entries = entry.next;
+            Some(entry)
+        })
+    }
+}
+
+impl<R> Entry<R> {
+    /// Hands the entry back to the registry for reuse.
+    #[inline]
+    pub(crate) fn release(&self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
+
+/// A type-erased node waiting to be freed, stamped with reclaimer-specific data.
+pub(crate) struct Retired {
+    pub(crate) ptr: *mut (),
+    pub(crate) stamp: usize,
+    drop: unsafe fn(*mut ()),
+    next: Option<NonNull<Retired>>,
+}
+
+impl Retired {
+    /// Drops the retired `Box<T>` and frees the node.
+    ///
+    /// # Safety
+    /// No thread may access the retired pointer anymore.
+    #[inline]
+    pub(crate) unsafe fn free(node: NonNull<Retired>) {
+        // SAFETY: nodes are allocated by `RetiredList::retire`, and the caller guarantees the
+        // pointer is no longer accessed.
+        unsafe {
+            let node = Box::from_raw(node.as_ptr());
+            (node.drop)(node.ptr);
+        }
+    }
+}
+
+/// A lock-free list of retired nodes.
+///
+/// Nodes are only ever pushed or taken all at once, so the list is not subject to ABA.
+pub(crate) struct RetiredList {
+    head: AtomicOptionNonNull<Retired>,
+    count: AtomicUsize,
+    /// The nodes the last scan could not free.
+    survivors: AtomicUsize,
+}
+
+impl Drop for RetiredList {
+    fn drop(&mut self) {
+        let mut retired = self.head.take(Ordering::Relaxed);
+        while let Some(node) = retired {
+            // SAFETY: the mutable reference guarantees no thread accesses the list, and the
+            // owner of the list guarantees no thread accesses the retired pointers anymore.
+            unsafe {
+                retired = node.as_ref().next;
+                Retired::free(node);
+            }
+        }
+    }
+}
+
+impl RetiredList {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: AtomicOptionNonNull::none(),
+            count: AtomicUsize::new(0),
+            survivors: AtomicUsize::new(0),
+        }
+    }
+
+    /// Retires `ptr` with `stamp`, returning whether the list should be scanned again.
+    ///
+    /// That is the case once `threshold` nodes plus twice the survivors of the last scan are
+    /// retired, so that nodes which cannot be freed yet, for example while a thread stalls, do
+    /// not make every retirement rescan them, keeping retirement amortized constant time.
+    ///
+    /// # Safety
+    /// `ptr` must come from `Box::into_raw`, must not be retired or freed otherwise, and dropping
+    /// the `Box<T>` on any thread at a later point must be sound.
+    pub(crate) unsafe fn retire<T>(&self, ptr: NonNull<T>, stamp: usize, threshold: usize) -> bool {
+        unsafe fn drop_box<T>(ptr: *mut ()) {
+            // SAFETY: `ptr` was retired as a `Box<T>`.
+            drop(unsafe { Box::from_raw(ptr.cast::<T>()) });
+        }
+        let node = NonNull::from(Box::leak(Box::new(Retired {
+            ptr: ptr.as_ptr().cast(),
+            stamp,
+            drop: drop_box::<T>,
+            next: None,
+        })));
+        // Count the node before publishing it, so a concurrent reclaim cannot free it first.
+        let count = self.count.fetch_add(1, Ordering::Relaxed) + 1;
+        self.push(node);
+        count >= 2 * self.survivors.load(Ordering::Relaxed) + threshold
+    }
+
+    /// Takes the whole list, then frees every node for which `is_safe` returns `true`.
+    ///
+    /// `snapshot` is called after the list was taken and a sequentially consistent fence, so it
+    /// observes every hazard published before the nodes were unlinked.
+    ///
+    /// # Safety
+    /// `is_safe` may only return `true` for nodes whose pointer is no longer accessed.
+    pub(crate) unsafe fn reclaim<S>(
+        &self,
+        snapshot: impl FnOnce() -> S,
+        mut is_safe: impl FnMut(&S, &Retired) -> bool,
+    ) {
+        let mut retired = self.head.take(Ordering::Acquire);
+        if retired.is_none() {
+            return;
+        }
+        atomic::fence(Ordering::SeqCst);
+        let snapshot = snapshot();
+
+        let (mut freed, mut survivors) = (0, 0);
+        while let Some(mut node) = retired {
+            // SAFETY: the list was taken by this thread, so it owns every node in it.
+            retired = unsafe { node.as_mut().next.take() };
+            // SAFETY: as above.
+            if is_safe(&snapshot, unsafe { node.as_ref() }) {
+                // SAFETY: the caller guarantees the pointer is no longer accessed.
+                unsafe { Retired::free(node) };
+                freed += 1;
+            } else {
+                self.push(node);
+                survivors += 1;
+            }
+        }
+        self.survivors.store(survivors, Ordering::Relaxed);
+        self.count.fetch_sub(freed, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    fn push(&self, mut node: NonNull<Retired>) {
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: the node is not shared until the exchange below succeeds.
+            unsafe { node.as_mut().next = head };
+            match self.head.compare_exchange_weak(
+                head,
+                Some(node),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use core::{
+    fmt::{Debug, Pointer},
+    ptr::NonNull,
+    sync::atomic::Ordering,
+};
+
+use crate::AtomicNonNull;
+
+/// An [`AtomicNonNull`] that packs a `BITS`-bit tag into the alignment bits of `T`.
+///
+/// `BITS` is checked at compile time to fit in the trailing zeros of `align_of::<T>()`.
+/// Tags passed to any method are truncated to `BITS` bits.
+///
+/// A `u8` has no alignment bits to spare, so even a single tag bit is rejected:
+///
+/// ```compile_fail
+/// # use core::ptr::NonNull;
+/// # use atomic_non_null::AtomicTaggedNonNull;
+/// let mut value = 0u8;
+/// AtomicTaggedNonNull::<u8, 1>::new(NonNull::from(&mut value), 0);
+/// ```
+#[repr(transparent)]
+pub struct AtomicTaggedNonNull<T, const BITS: u32> {
+    ptr: AtomicNonNull<T>,
+}
+
+impl<T, const BITS: u32> Debug for AtomicTaggedNonNull<T, BITS> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (ptr, tag) = self.load(Ordering::Relaxed);
+        f.debug_struct("AtomicTaggedNonNull")
+            .field("ptr", &ptr)
+            .field("tag", &tag)
+            .finish()
+    }
+}
+
+impl<T, const BITS: u32> Pointer for AtomicTaggedNonNull<T, BITS> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.load(Ordering::Relaxed).0, f)
+    }
+}
+
+impl<T, const BITS: u32> AtomicTaggedNonNull<T, BITS> {
+    /// The bits of the address used to store the tag.
+    pub const TAG_MASK: usize = {
+        assert!(
+            BITS <= align_of::<T>().trailing_zeros(),
+            "`BITS` does not fit in the alignment of `T`"
+        );
+        (1 << BITS) - 1
+    };
+
+    /// # Panics
+    /// Panics if `ptr` is not aligned to `1 << BITS`.
+    #[inline]
+    pub fn new(ptr: NonNull<T>, tag: usize) -> Self {
+        Self {
+            ptr: AtomicNonNull::from_non_null(Self::compose(ptr, tag)),
+        }
+    }
+
+    /// Look at [`AtomicNonNull::into_inner`] for more information.
+    #[inline]
+    pub fn into_inner(self) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.into_inner())
+    }
+
+    /// Look at [`AtomicNonNull::load`] for more information.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.load(order))
+    }
+
+    /// Look at [`AtomicNonNull::store`] for more information.
+    ///
+    /// # Panics
+    /// Panics if `ptr` is not aligned to `1 << BITS`.
+    #[inline]
+    pub fn store(&self, ptr: NonNull<T>, tag: usize, order: Ordering) {
+        self.ptr.store(Self::compose(ptr, tag), order);
+    }
+
+    /// Look at [`AtomicNonNull::swap`] for more information.
+    ///
+    /// # Panics
+    /// Panics if `ptr` is not aligned to `1 << BITS`.
+    #[inline]
+    pub fn swap(&self, ptr: NonNull<T>, tag: usize, order: Ordering) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.swap(Self::compose(ptr, tag), order))
+    }
+
+    /// Compares the pointer and the tag together.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange`] for more information.
+    ///
+    /// # Panics
+    /// Panics if either pointer is not aligned to `1 << BITS`.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: (NonNull<T>, usize),
+        new: (NonNull<T>, usize),
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(NonNull<T>, usize), (NonNull<T>, usize)> {
+        self.ptr
+            .compare_exchange(
+                Self::compose(current.0, current.1),
+                Self::compose(new.0, new.1),
+                success,
+                failure,
+            )
+            .map(Self::decompose)
+            .map_err(Self::decompose)
+    }
+
+    /// Compares the pointer and the tag together.
+    ///
+    /// Look at [`AtomicNonNull::compare_exchange_weak`] for more information.
+    ///
+    /// # Panics
+    /// Panics if either pointer is not aligned to `1 << BITS`.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: (NonNull<T>, usize),
+        new: (NonNull<T>, usize),
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<(NonNull<T>, usize), (NonNull<T>, usize)> {
+        self.ptr
+            .compare_exchange_weak(
+                Self::compose(current.0, current.1),
+                Self::compose(new.0, new.1),
+                success,
+                failure,
+            )
+            .map(Self::decompose)
+            .map_err(Self::decompose)
+    }
+
+    /// Bitwise "or" of the current tag with `tag`, returning the previous pointer and tag.
+    #[inline]
+    pub fn fetch_or_tag(&self, tag: usize, order: Ordering) -> (NonNull<T>, usize) {
+        Self::decompose(self.ptr.fetch_or(tag & Self::TAG_MASK, order))
+    }
+
+    /// Bitwise "and" of the current tag with `tag`, returning the previous pointer and tag.
+    #[inline]
+    pub fn fetch_and_tag(&self, tag: usize, order: Ordering) -> (NonNull<T>, usize) {
+        // SAFETY: the pointer bits are left untouched, and they are never all zero.
+        Self::decompose(unsafe { self.ptr.fetch_and_unchecked(tag | !Self::TAG_MASK, order) })
+    }
+
+    #[inline]
+    fn compose(ptr: NonNull<T>, tag: usize) -> NonNull<T> {
+        assert!(
+            ptr.addr().get() & Self::TAG_MASK == 0,
+            "pointer is not aligned enough to store the tag"
+        );
+        // SAFETY: or-ing bits into a non-null address keeps it non-null.
+        unsafe {
+            NonNull::new_unchecked(ptr.as_ptr().map_addr(|addr| addr | (tag & Self::TAG_MASK)))
+        }
+    }
+
+    #[inline]
+    fn decompose(ptr: NonNull<T>) -> (NonNull<T>, usize) {
+        let tag = ptr.addr().get() & Self::TAG_MASK;
+        // SAFETY: only aligned, non-null pointers are stored, so clearing the tag bits leaves a
+        // non-null address.
+        let ptr =
+            unsafe { NonNull::new_unchecked(ptr.as_ptr().map_addr(|addr| addr & !Self::TAG_MASK)) };
+        (ptr, tag)
+    }
+}
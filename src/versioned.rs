@@ -0,0 +1,249 @@
+use core::{
+    cell::UnsafeCell,
+    fmt::{Debug, Pointer},
+    ptr::{self, NonNull},
+    sync::atomic::Ordering,
+};
+
+use crate::atomic::AtomicBool;
+
+/// A non-null pointer paired with a generation counter, both updated in a single atomic step.
+///
+/// Every successful update increments the version, so a compare-exchange against a stale
+/// `(pointer, version)` pair fails even if the same address was stored again in between.
+///
+/// On x86_64 CPUs with `cmpxchg16b` the pair is updated with a double-width compare-exchange.
+/// Elsewhere every operation goes through a spinlock, which building with
+/// `RUSTFLAGS="--cfg atomic_non_null_no_cmpxchg16b"` selects on x86_64 as well. Either way operations are sequentially
+/// consistent, so the orderings passed in only act as a lower bound.
+pub struct AtomicVersionedNonNull<T> {
+    pair: UnsafeCell<Pair<T>>,
+    lock: AtomicBool,
+}
+
+// SAFETY: all accesses to `pair` are atomic or serialized by `lock`, just like `AtomicPtr<T>`.
+unsafe impl<T> Send for AtomicVersionedNonNull<T> {}
+// SAFETY: all accesses to `pair` are atomic or serialized by `lock`, just like `AtomicPtr<T>`.
+unsafe impl<T> Sync for AtomicVersionedNonNull<T> {}
+
+#[repr(C, align(16))]
+struct Pair<T> {
+    ptr: *mut T,
+    version: usize,
+}
+
+impl<T> Clone for Pair<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Pair<T> {}
+
+impl<T> PartialEq for Pair<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.ptr, other.ptr) && self.version == other.version
+    }
+}
+
+impl<T> Debug for AtomicVersionedNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (ptr, version) = self.load(Ordering::Relaxed);
+        f.debug_struct("AtomicVersionedNonNull")
+            .field("ptr", &ptr)
+            .field("version", &version)
+            .finish()
+    }
+}
+
+impl<T> Pointer for AtomicVersionedNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Pointer::fmt(&self.load(Ordering::Relaxed).0, f)
+    }
+}
+
+impl<T> AtomicVersionedNonNull<T> {
+    /// Creates a new pointer with a version of zero.
+    #[inline]
+    pub const fn new(ptr: NonNull<T>) -> Self {
+        Self::with_version(ptr, 0)
+    }
+
+    #[inline]
+    pub const fn with_version(ptr: NonNull<T>, version: usize) -> Self {
+        Self {
+            pair: UnsafeCell::new(Pair {
+                ptr: ptr.as_ptr(),
+                version,
+            }),
+            lock: AtomicBool::new(false),
+        }
+    }
+
+    /// Look at [`crate::AtomicNonNull::into_inner`] for more information.
+    #[inline]
+    pub fn into_inner(self) -> (NonNull<T>, usize) {
+        Self::decompose(self.pair.into_inner())
+    }
+
+    /// Look at [`crate::AtomicNonNull::load`] for more information.
+    #[inline]
+    pub fn load(&self, _order: Ordering) -> (NonNull<T>, usize) {
+        // A null pointer is never stored, so this never succeeds and returns the current pair.
+        let null = Pair {
+            ptr: ptr::null_mut(),
+            version: 0,
+        };
+        match self.compare_exchange_pair(null, null) {
+            Ok(pair) | Err(pair) => Self::decompose(pair),
+        }
+    }
+
+    /// Stores `ptr` and increments the version.
+    ///
+    /// Look at [`crate::AtomicNonNull::store`] for more information.
+    #[inline]
+    pub fn store(&self, ptr: NonNull<T>, order: Ordering) {
+        self.swap(ptr, order);
+    }
+
+    /// Stores `ptr` and increments the version, returning the previous pair.
+    ///
+    /// Look at [`crate::AtomicNonNull::swap`] for more information.
+    #[inline]
+    pub fn swap(&self, ptr: NonNull<T>, order: Ordering) -> (NonNull<T>, usize) {
+        let mut current = self.load(order);
+        loop {
+            match self.compare_exchange(current, ptr, order, order) {
+                Ok(prev) => return prev,
+                Err(prev) => current = prev,
+            }
+        }
+    }
+
+    /// Stores `new` and increments the version if the current pointer and version equal
+    /// `current`.
+    ///
+    /// Look at [`crate::AtomicNonNull::compare_exchange`] for more information.
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: (NonNull<T>, usize),
+        new: NonNull<T>,
+        _success: Ordering,
+        _failure: Ordering,
+    ) -> Result<(NonNull<T>, usize), (NonNull<T>, usize)> {
+        let current = Pair {
+            ptr: current.0.as_ptr(),
+            version: current.1,
+        };
+        let new = Pair {
+            ptr: new.as_ptr(),
+            version: current.version.wrapping_add(1),
+        };
+        self.compare_exchange_pair(current, new)
+            .map(Self::decompose)
+            .map_err(Self::decompose)
+    }
+
+    #[inline]
+    fn compare_exchange_pair(&self, current: Pair<T>, new: Pair<T>) -> Result<Pair<T>, Pair<T>> {
+        #[cfg(all(target_arch = "x86_64", not(atomic_non_null_no_cmpxchg16b)))]
+        if cmpxchg16b::is_available() {
+            // SAFETY: `pair` is 16-byte aligned and only ever accessed atomically on this path.
+            return unsafe { cmpxchg16b::compare_exchange(self.pair.get(), current, new) };
+        }
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            crate::atomic::spin_loop();
+        }
+        // SAFETY: the lock is held, so no other thread accesses `pair`.
+        let pair = unsafe { &mut *self.pair.get() };
+        let prev = *pair;
+        if prev == current {
+            *pair = new;
+        }
+        self.lock.store(false, Ordering::SeqCst);
+        if prev == current { Ok(prev) } else { Err(prev) }
+    }
+
+    #[inline]
+    fn decompose(pair: Pair<T>) -> (NonNull<T>, usize) {
+        // SAFETY: only non-null pointers are stored.
+        (unsafe { NonNull::new_unchecked(pair.ptr) }, pair.version)
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", not(atomic_non_null_no_cmpxchg16b)))]
+mod cmpxchg16b {
+    use core::{
+        arch::{asm, x86_64::__cpuid},
+        ptr,
+        sync::atomic::{AtomicU8, Ordering},
+    };
+
+    use super::Pair;
+
+    const UNKNOWN: u8 = 0;
+    const MISSING: u8 = 1;
+    const PRESENT: u8 = 2;
+
+    static STATE: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+    /// Whether the CPU supports `cmpxchg16b`, detected once and then cached.
+    #[inline]
+    pub(super) fn is_available() -> bool {
+        if cfg!(target_feature = "cmpxchg16b") {
+            return true;
+        }
+        match STATE.load(Ordering::Relaxed) {
+            UNKNOWN => {
+                let present = __cpuid(1).ecx & (1 << 13) != 0;
+                STATE.store(if present { PRESENT } else { MISSING }, Ordering::Relaxed);
+                present
+            }
+            state => state == PRESENT,
+        }
+    }
+
+    /// # Safety
+    /// `dst` must be valid, 16-byte aligned and only ever accessed through this function.
+    #[inline]
+    pub(super) unsafe fn compare_exchange<T>(
+        dst: *mut Pair<T>,
+        current: Pair<T>,
+        new: Pair<T>,
+    ) -> Result<Pair<T>, Pair<T>> {
+        let (prev_lo, prev_hi): (usize, usize);
+        // SAFETY: the caller guarantees `dst` is valid and aligned, and the CPU supports the
+        // instruction. `rbx` is reserved by LLVM, so it is swapped in and restored around it.
+        // `dst` is pinned to `rsi`, as a register chosen by LLVM could be `rbx` itself and be
+        // overwritten by the swap.
+        unsafe {
+            asm!(
+                "xchg {new_lo}, rbx",
+                "lock cmpxchg16b xmmword ptr [rsi]",
+                "mov rbx, {new_lo}",
+                in("rsi") dst,
+                new_lo = inout(reg) new.ptr.expose_provenance() => _,
+                in("rcx") new.version,
+                inout("rax") current.ptr.expose_provenance() => prev_lo,
+                inout("rdx") current.version => prev_hi,
+                options(nostack),
+            );
+        }
+        let prev = Pair {
+            ptr: ptr::with_exposed_provenance_mut(prev_lo),
+            version: prev_hi,
+        };
+        if prev == current { Ok(prev) } else { Err(prev) }
+    }
+}
@@ -0,0 +1,129 @@
+#![cfg(all(not(loom), not(feature = "shuttle")))]
+
+use std::{ptr::NonNull, sync::atomic::Ordering};
+
+use atomic_non_null::AtomicNonNull;
+
+#[test]
+fn pointer_arithmetic() {
+    let mut values = [0u64; 4];
+    let [a, b, c, _] = values.each_mut().map(NonNull::from);
+    let ptr = AtomicNonNull::from_non_null(a);
+
+    assert_eq!(ptr.fetch_ptr_add(2, Ordering::Relaxed), Ok(a));
+    assert_eq!(ptr.load(Ordering::Relaxed), c);
+    assert_eq!(ptr.fetch_ptr_sub(1, Ordering::Relaxed), Ok(c));
+    assert_eq!(ptr.fetch_byte_add(8, Ordering::Relaxed), Ok(b));
+    assert_eq!(ptr.fetch_byte_sub(16, Ordering::Relaxed), Ok(c));
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+
+    // SAFETY: none of the results is null.
+    unsafe {
+        assert_eq!(ptr.fetch_ptr_add_unchecked(1, Ordering::Relaxed), a);
+        assert_eq!(ptr.fetch_byte_add_unchecked(8, Ordering::Relaxed), b);
+        assert_eq!(ptr.fetch_ptr_sub_unchecked(1, Ordering::Relaxed), c);
+        assert_eq!(ptr.fetch_byte_sub_unchecked(8, Ordering::Relaxed), b);
+    }
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+}
+
+/// Arithmetic that would wrap around to null is refused and leaves the pointer unchanged.
+#[test]
+fn arithmetic_refuses_null() {
+    let mut value = 0u64;
+    let a = NonNull::from(&mut value);
+    let addr = a.addr().get();
+    let ptr = AtomicNonNull::from_non_null(a);
+
+    assert_eq!(ptr.fetch_ptr_sub(addr / 8, Ordering::Relaxed), Err(a));
+    assert_eq!(ptr.fetch_byte_sub(addr, Ordering::Relaxed), Err(a));
+    assert_eq!(
+        ptr.fetch_byte_add(addr.wrapping_neg(), Ordering::Relaxed),
+        Err(a)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+
+    let mut byte = 0u8;
+    let byte = NonNull::from(&mut byte);
+    let ptr = AtomicNonNull::from_non_null(byte);
+    let addr = byte.addr().get();
+    assert_eq!(
+        ptr.fetch_ptr_add(addr.wrapping_neg(), Ordering::Relaxed),
+        Err(byte)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), byte);
+}
+
+#[test]
+fn bitwise_operations() {
+    let mut value = 0u64;
+    let a = NonNull::from(&mut value);
+    let addr = |ptr: NonNull<u64>| ptr.addr().get();
+    let ptr = AtomicNonNull::from_non_null(a);
+
+    assert_eq!(ptr.fetch_or(0b1, Ordering::Relaxed), a);
+    assert_eq!(addr(ptr.load(Ordering::Relaxed)), addr(a) | 0b1);
+    assert_eq!(
+        ptr.fetch_xor(0b11, Ordering::Relaxed).map(addr),
+        Ok(addr(a) | 0b1)
+    );
+    assert_eq!(addr(ptr.load(Ordering::Relaxed)), addr(a) | 0b10);
+    assert_eq!(
+        ptr.fetch_and(!0b10, Ordering::Relaxed).map(addr),
+        Ok(addr(a) | 0b10)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+
+    // SAFETY: none of the results is null.
+    unsafe {
+        assert_eq!(ptr.fetch_xor_unchecked(0b100, Ordering::Relaxed), a);
+        let prev = ptr.fetch_and_unchecked(!0b100, Ordering::Relaxed);
+        assert_eq!(addr(prev), addr(a) | 0b100);
+    }
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+}
+
+/// Bitwise operations that would clear every bit are refused and leave the pointer unchanged.
+#[test]
+fn bitwise_operations_refuse_null() {
+    let mut value = 0u64;
+    let a = NonNull::from(&mut value);
+    let ptr = AtomicNonNull::from_non_null(a);
+
+    assert_eq!(ptr.fetch_and(0, Ordering::Relaxed), Err(a));
+    assert_eq!(ptr.fetch_and(0b111, Ordering::Relaxed), Err(a));
+    assert_eq!(ptr.fetch_xor(a.addr().get(), Ordering::Relaxed), Err(a));
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+}
+
+#[test]
+fn update() {
+    let mut values = [0u64; 2];
+    let [a, b] = values.each_mut().map(NonNull::from);
+    let ptr = AtomicNonNull::from_non_null(a);
+    assert_eq!(ptr.update(Ordering::Relaxed, Ordering::Relaxed, |_| b), a);
+    assert_eq!(ptr.load(Ordering::Relaxed), b);
+}
+
+#[test]
+fn try_update() {
+    let mut values = [0u64; 2];
+    let [a, b] = values.each_mut().map(NonNull::from);
+    let ptr = AtomicNonNull::from_non_null(a);
+
+    let refused = ptr.try_update(Ordering::Relaxed, Ordering::Relaxed, |_| Err("refused"));
+    assert_eq!(refused, Err((a, "refused")));
+    assert_eq!(ptr.load(Ordering::Relaxed), a);
+
+    assert_eq!(
+        ptr.try_update(Ordering::Relaxed, Ordering::Relaxed, |_| Ok::<_, ()>(b)),
+        Ok(a)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), b);
+
+    assert_eq!(
+        ptr.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| None),
+        Err(b)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), b);
+}
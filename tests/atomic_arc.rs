@@ -0,0 +1,125 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use atomic_non_null::AtomicArc;
+use common::Tracked;
+
+mod common;
+
+const READERS: usize = 8;
+const WRITERS: usize = 2;
+const PER_WRITER: usize = 10_000;
+
+fn tracked(value: usize, drops: &Arc<AtomicUsize>) -> Arc<Tracked> {
+    Arc::new(Tracked::new(value, drops))
+}
+
+#[test]
+fn single_threaded() {
+    let cell = AtomicArc::new(1);
+    assert_eq!(*cell.load(), 1);
+
+    let two = Arc::new(2);
+    cell.store(two.clone());
+    assert_eq!(Arc::strong_count(&two), 2);
+    assert!(Arc::ptr_eq(&cell.load(), &two));
+
+    let prev = cell.swap(Arc::new(3));
+    assert!(Arc::ptr_eq(&prev, &two));
+    assert_eq!(Arc::strong_count(&two), 2);
+    drop(prev);
+    assert_eq!(Arc::strong_count(&two), 1);
+
+    let (current, four) = cell.compare_and_swap(&two, Arc::new(4)).unwrap_err();
+    assert_eq!(*four, 4);
+    assert!(std::ptr::eq(current.as_ptr(), &*cell.load()));
+
+    let three = cell.load();
+    let prev = cell.compare_and_swap(&three, four).unwrap();
+    assert!(Arc::ptr_eq(&prev, &three));
+    assert_eq!(*cell.into_inner(), 4);
+}
+
+#[test]
+fn drops_every_value() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let cell = AtomicArc::from(tracked(0, &drops));
+    for value in 1..10 {
+        cell.store(tracked(value, &drops));
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 9);
+    let last = cell.load();
+    drop(cell);
+    assert_eq!(drops.load(Ordering::Relaxed), 9);
+    drop(last);
+    assert_eq!(drops.load(Ordering::Relaxed), 10);
+}
+
+/// Readers only ever see live values while writers replace them, and every value is dropped
+/// exactly once in the end.
+#[test]
+fn stress() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let cell = AtomicArc::from(tracked(0, &drops));
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        for _ in 0..READERS {
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    cell.load().check();
+                }
+            });
+        }
+        let writers = (0..WRITERS)
+            .map(|writer| {
+                let (cell, drops) = (&cell, &drops);
+                s.spawn(move || {
+                    for seq in 0..PER_WRITER {
+                        let value = 1 + writer * PER_WRITER + seq;
+                        if seq % 2 == 0 {
+                            cell.store(tracked(value, drops));
+                        } else {
+                            let current = cell.load();
+                            let _ = cell.compare_and_swap(&current, tracked(value, drops));
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    drop(cell);
+    assert_eq!(drops.load(Ordering::Relaxed), 1 + WRITERS * PER_WRITER);
+}
+
+/// Writers finish while readers load continuously, since they only wait for the loads that
+/// could have seen the value they replaced.
+#[test]
+fn writers_progress_under_continuous_reads() {
+    let cell = AtomicArc::new(0);
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        for _ in 0..READERS {
+            s.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    drop(cell.load());
+                }
+            });
+        }
+        for value in 1..=1_000 {
+            cell.store(Arc::new(value));
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(*cell.load(), 1_000);
+}
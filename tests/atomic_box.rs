@@ -0,0 +1,84 @@
+#![cfg(all(feature = "alloc", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use atomic_non_null::AtomicBox;
+use common::Tracked;
+
+mod common;
+
+fn tracked(value: usize, drops: &Arc<AtomicUsize>) -> Box<Tracked> {
+    Box::new(Tracked::new(value, drops))
+}
+
+#[test]
+fn swap_and_store() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let cell = AtomicBox::from(tracked(0, &drops));
+    let prev = cell.swap(tracked(1, &drops), Ordering::AcqRel);
+    assert_eq!(prev.check(), 0);
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+    drop(prev);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    cell.store(tracked(2, &drops), Ordering::Release);
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+    // SAFETY: the cell owns the box and nothing replaces it concurrently.
+    assert_eq!(unsafe { cell.load(Ordering::Acquire).as_ref() }.check(), 2);
+}
+
+/// A failed exchange hands the rejected box back instead of dropping or leaking it.
+#[test]
+fn compare_exchange_hands_back_the_rejected_box() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let cell = AtomicBox::from(tracked(0, &drops));
+    let current = cell.load(Ordering::Acquire);
+
+    let (actual, rejected) = cell
+        .compare_exchange(
+            NonNull::dangling(),
+            tracked(1, &drops),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        )
+        .unwrap_err();
+    assert_eq!(actual, current);
+    assert_eq!(rejected.check(), 1);
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    let prev = cell
+        .compare_exchange(current, rejected, Ordering::AcqRel, Ordering::Acquire)
+        .unwrap();
+    assert_eq!(prev.check(), 0);
+    drop(prev);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+    assert_eq!(cell.into_inner().check(), 1);
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+}
+
+/// Dropping the cell frees its box exactly once, and `into_inner` hands it over instead.
+#[test]
+fn drops_the_pointee_once() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    drop(AtomicBox::from(tracked(0, &drops)));
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    let mut cell = AtomicBox::from(tracked(1, &drops));
+    *cell.get_mut() = Tracked::new(2, &drops);
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+    let inner = cell.into_inner();
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+    assert_eq!(inner.check(), 2);
+    drop(inner);
+    assert_eq!(drops.load(Ordering::Relaxed), 3);
+
+    let cell = AtomicBox::new(vec![1, 2, 3]);
+    assert_eq!(*cell.into_inner(), [1, 2, 3]);
+    assert_eq!(*AtomicBox::<Vec<u8>>::default().into_inner(), []);
+}
@@ -0,0 +1,78 @@
+//! Fixtures shared by the integration tests, each of which uses only some of them.
+
+#![allow(dead_code, unused_macros)]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// A value that checks its own integrity and counts how often it was dropped.
+#[derive(Debug)]
+pub struct Tracked {
+    pub value: usize,
+    double: usize,
+    drops: Arc<AtomicUsize>,
+}
+
+impl Tracked {
+    pub fn new(value: usize, drops: &Arc<AtomicUsize>) -> Self {
+        Self {
+            value,
+            double: value * 2,
+            drops: drops.clone(),
+        }
+    }
+
+    /// Panics if the value was overwritten, as happens to freed memory, and returns it otherwise.
+    pub fn check(&self) -> usize {
+        assert_eq!(self.value * 2, self.double);
+        self.value
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.check();
+        self.drops.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Defines a test running `$stress` on `$collection` for each reclaimer.
+macro_rules! stress_reclaimers {
+    ($stress:ident, $collection:ident) => {
+        #[test]
+        fn stress_hazard() {
+            $stress($collection::<_, ::atomic_non_null::hazard::Domain>::new());
+        }
+
+        #[test]
+        fn stress_era() {
+            $stress($collection::<_, ::atomic_non_null::era::Domain>::new());
+        }
+
+        #[test]
+        fn stress_qsbr() {
+            $stress($collection::<_, ::atomic_non_null::qsbr::Domain>::new());
+        }
+
+        #[test]
+        fn stress_epoch() {
+            $stress($collection::with_reclaimer(::atomic_non_null::epoch::Epoch));
+        }
+
+        #[test]
+        fn stress_leak() {
+            $stress($collection::with_reclaimer(
+                ::atomic_non_null::reclaim::Leak,
+            ));
+        }
+
+        #[test]
+        fn stress_shared_domain() {
+            $stress($collection::with_reclaimer(
+                ::atomic_non_null::hazard::Domain::global(),
+            ));
+        }
+    };
+}
@@ -0,0 +1,157 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    ptr::NonNull,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use atomic_non_null::{
+    AtomicNonNull,
+    epoch::{self, Epoch},
+    reclaim::Reclaimer,
+};
+use common::Tracked;
+
+mod common;
+
+/// The epoch is global, so tests counting its advances must not run concurrently.
+fn serial() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn tracked(value: usize, drops: &Arc<AtomicUsize>) -> NonNull<Tracked> {
+    NonNull::from(Box::leak(Box::new(Tracked::new(value, drops))))
+}
+
+/// Tries to advance the epoch once from an otherwise unpinned thread.
+fn flush() {
+    epoch::pin().flush();
+}
+
+#[test]
+fn frees_after_two_epoch_advances() {
+    let _serial = serial();
+    let drops = Arc::new(AtomicUsize::new(0));
+    // SAFETY: the node comes from `Box::leak` and was never shared.
+    unsafe { epoch::pin().defer_destroy(tracked(0, &drops)) };
+
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn pinned_guards_hold_back_reclamation() {
+    let _serial = serial();
+    let drops = Arc::new(AtomicUsize::new(0));
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+
+    let guard = epoch::pin();
+    let shared = src.load_guarded(&guard);
+    let prev = src.swap(tracked(1, &drops), Ordering::AcqRel);
+    assert_eq!(prev, shared.as_non_null());
+    // SAFETY: `prev` comes from `Box::leak` and was unlinked by the swap.
+    unsafe { guard.defer_destroy(prev) };
+    for _ in 0..10 {
+        guard.flush();
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+    // SAFETY: the guard keeps the node alive.
+    assert_eq!(unsafe { shared.as_ref() }.check(), 0);
+
+    drop(guard);
+    flush();
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    // SAFETY: the last pointer comes from `Box::leak` and is only owned by `src`.
+    drop(unsafe { Box::from_raw(src.into_inner().as_ptr()) });
+}
+
+/// The thread stays pinned until the outermost guard is dropped.
+#[test]
+fn nested_guards() {
+    let _serial = serial();
+    let drops = Arc::new(AtomicUsize::new(0));
+    let outer = epoch::pin();
+    let inner = epoch::pin();
+    // SAFETY: the node comes from `Box::leak` and was never shared.
+    unsafe { inner.defer_destroy(tracked(0, &drops)) };
+    drop(inner);
+    for _ in 0..10 {
+        outer.flush();
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    drop(outer);
+    flush();
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn load_guarded() {
+    let _serial = serial();
+    let mut value = 0;
+    let src = AtomicNonNull::from_non_null(NonNull::from(&mut value));
+    let guard = epoch::pin();
+    let shared = src.load_guarded(&guard);
+    let copy = shared;
+    assert_eq!(shared, copy);
+    assert_eq!(shared.as_non_null(), src.load(Ordering::Relaxed));
+    // SAFETY: `value` outlives the guard.
+    assert_eq!(unsafe { *shared.as_ref() }, 0);
+}
+
+/// The epoch reclaimer keeps the thread pinned for as long as a protected pointer is alive.
+#[test]
+fn reclaimer_guards_keep_the_thread_pinned() {
+    let _serial = serial();
+    let drops = Arc::new(AtomicUsize::new(0));
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+
+    let protected = Epoch.protect(&src);
+    let prev = src.swap(tracked(1, &drops), Ordering::AcqRel);
+    // SAFETY: `prev` comes from `Box::leak` and was unlinked by the swap.
+    unsafe { Epoch.retire(prev) };
+    for _ in 0..10 {
+        protected.guard().flush();
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    drop(protected);
+    flush();
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    // SAFETY: the last pointer comes from `Box::leak` and is only owned by `src`.
+    drop(unsafe { Box::from_raw(src.into_inner().as_ptr()) });
+}
+
+/// Threads that exited release their participant, so they neither hold back reclamation nor
+/// keep the participants of later threads from being reused.
+#[test]
+fn exited_threads_do_not_hold_back_reclamation() {
+    let _serial = serial();
+    let drops = Arc::new(AtomicUsize::new(0));
+    for value in 0..100 {
+        let drops = drops.clone();
+        thread::spawn(move || {
+            let guard = epoch::pin();
+            // SAFETY: the node comes from `Box::leak` and was never shared.
+            unsafe { guard.defer_destroy(tracked(value, &drops)) };
+        })
+        .join()
+        .unwrap();
+    }
+
+    flush();
+    flush();
+    assert_eq!(drops.load(Ordering::Relaxed), 100);
+}
@@ -0,0 +1,125 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use atomic_non_null::{AtomicNonNull, era::Domain};
+use common::Tracked;
+
+mod common;
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 10_000;
+
+/// Frees the pointer left in `src`, which must have been allocated in `domain`.
+fn free(domain: &Domain, src: AtomicNonNull<Tracked>) {
+    // SAFETY: the last pointer is only owned by `src` and comes from `domain`.
+    unsafe { domain.retire(src.into_inner()) };
+    domain.reclaim();
+}
+
+#[test]
+fn frees_once_the_guard_is_dropped() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let src = AtomicNonNull::from_non_null(domain.alloc(Tracked::new(0, &drops)));
+
+    let guard = domain.protect(&src);
+    let prev = src.swap(domain.alloc(Tracked::new(1, &drops)), Ordering::AcqRel);
+    assert_eq!(prev, guard.as_non_null());
+    // SAFETY: `prev` was unlinked by the swap.
+    unsafe { domain.retire(prev) };
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+    // SAFETY: the guard keeps the node alive.
+    assert_eq!(unsafe { guard.as_ref() }.check(), 0);
+
+    drop(guard);
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    free(&domain, src);
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+}
+
+/// A guard only holds back the nodes that were alive in the era it published, not nodes
+/// allocated after it.
+#[test]
+fn guards_only_hold_back_nodes_alive_in_their_era() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let src = AtomicNonNull::from_non_null(domain.alloc(Tracked::new(0, &drops)));
+
+    let guard = domain.protect(&src);
+    for value in 1..10 {
+        let prev = src.swap(domain.alloc(Tracked::new(value, &drops)), Ordering::AcqRel);
+        // SAFETY: `prev` was unlinked by the swap.
+        unsafe { domain.retire(prev) };
+    }
+    domain.reclaim();
+    // The first replacement was allocated in the guard's era as well.
+    assert_eq!(drops.load(Ordering::Relaxed), 7);
+    // SAFETY: the guard keeps the node alive.
+    assert_eq!(unsafe { guard.as_ref() }.check(), 0);
+
+    drop(guard);
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 9);
+
+    free(&domain, src);
+    assert_eq!(drops.load(Ordering::Relaxed), 10);
+}
+
+#[test]
+fn dropping_the_domain_frees_retired_nodes() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let value = domain.alloc(Tracked::new(0, &drops));
+    let guard = domain.protect(&AtomicNonNull::from_non_null(value));
+    // SAFETY: the node was never shared.
+    unsafe { domain.retire(value) };
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    drop(guard);
+    drop(domain);
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+}
+
+/// Threads read the current value while others replace and retire it, and every value is freed
+/// exactly once in the end.
+#[test]
+fn stress() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let src = AtomicNonNull::from_non_null(domain.alloc(Tracked::new(0, &drops)));
+    thread::scope(|s| {
+        for thread in 0..THREADS {
+            let (domain, src, drops) = (&domain, &src, &drops);
+            s.spawn(move || {
+                for seq in 0..PER_THREAD {
+                    let value = 1 + thread * PER_THREAD + seq;
+                    let guard = domain.protect(src);
+                    // SAFETY: the guard keeps the node alive.
+                    assert!(unsafe { guard.as_ref() }.check() <= THREADS * PER_THREAD);
+                    if seq % 4 == 0 {
+                        let new = domain.alloc(Tracked::new(value, drops));
+                        let prev = src.swap(new, Ordering::AcqRel);
+                        // SAFETY: `prev` was unlinked by the swap.
+                        unsafe { domain.retire(prev) };
+                    }
+                }
+            });
+        }
+    });
+    free(&domain, src);
+    assert_eq!(
+        drops.load(Ordering::Relaxed),
+        1 + THREADS * PER_THREAD.div_ceil(4)
+    );
+}
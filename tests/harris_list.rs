@@ -0,0 +1,148 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    collections::BTreeSet,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicIsize, Ordering},
+    },
+    thread,
+};
+
+use atomic_non_null::{collections::HarrisList, era, reclaim::Reclaimer};
+
+#[macro_use]
+mod common;
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 200;
+const SHARED: usize = 16;
+const ROUNDS: usize = 2_000;
+
+/// Threads insert and remove keys of their own alongside keys contended by all of them, while a
+/// reader iterates. Every operation's result must be consistent with a sequential set.
+fn stress<R: Reclaimer + Send + Sync>(list: HarrisList<usize, R>) {
+    // Own keys come after the shared ones, and each thread owns every `THREADS`th of them.
+    let own = |thread: usize| (0..PER_THREAD).map(move |i| SHARED + i * THREADS + thread);
+    let net = (0..SHARED).map(|_| AtomicIsize::new(0)).collect::<Vec<_>>();
+    let done = AtomicBool::new(false);
+    thread::scope(|s| {
+        s.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                let mut last = None;
+                for value in &list {
+                    assert!(last < Some(*value));
+                    last = Some(*value);
+                }
+            }
+        });
+        let writers = (0..THREADS)
+            .map(|thread| {
+                let (list, net) = (&list, &net);
+                s.spawn(move || {
+                    for key in own(thread) {
+                        assert!(list.insert(key));
+                        assert!(!list.insert(key));
+                        assert!(list.contains(&key));
+                    }
+                    for round in 0..ROUNDS {
+                        let key = (round * 7 + thread) % SHARED;
+                        if round % 2 == 0 {
+                            if list.insert(key) {
+                                net[key].fetch_add(1, Ordering::Relaxed);
+                            }
+                        } else if list.remove(&key) {
+                            net[key].fetch_sub(1, Ordering::Relaxed);
+                        }
+                    }
+                    for key in own(thread).filter(|key| key % 2 == 0) {
+                        assert!(list.remove(&key));
+                        assert!(!list.remove(&key));
+                        assert!(!list.contains(&key));
+                    }
+                    for key in own(thread).filter(|key| key % 2 == 1) {
+                        assert_eq!(list.get(&key).as_deref(), Some(&key));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+
+    let mut expected = (0..THREADS)
+        .flat_map(own)
+        .filter(|key| key % 2 == 1)
+        .collect::<BTreeSet<_>>();
+    for (key, net) in net.iter().enumerate() {
+        match net.load(Ordering::Relaxed) {
+            0 => {}
+            1 => {
+                expected.insert(key);
+            }
+            net => panic!("key {key} was inserted {net} more times than it was removed"),
+        }
+    }
+    assert_eq!(
+        list.iter().map(|value| *value).collect::<Vec<_>>(),
+        expected.iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(list.len_estimate(), expected.len());
+}
+
+#[test]
+fn ordered_set() {
+    let list = HarrisList::<usize>::new();
+    assert!(list.is_empty());
+    for key in [5, 1, 4, 2, 3] {
+        assert!(list.insert(key));
+    }
+    assert!(!list.insert(3));
+    assert_eq!(list.len_estimate(), 5);
+    assert_eq!(
+        list.iter().map(|value| *value).collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+
+    assert!(list.contains(&4));
+    assert!(list.remove(&4));
+    assert!(!list.remove(&4));
+    assert!(!list.contains(&4));
+    assert_eq!(list.get(&4).as_deref(), None);
+    assert_eq!(list.get(&5).as_deref(), Some(&5));
+    assert_eq!(
+        list.iter().map(|value| *value).collect::<Vec<_>>(),
+        [1, 2, 3, 5]
+    );
+    assert_eq!(list.len_estimate(), 4);
+}
+
+#[test]
+fn references_outlive_removal() {
+    let list = HarrisList::<String>::new();
+    list.insert("a".to_owned());
+    let value = list.get("a").unwrap();
+    assert!(list.remove("a"));
+    assert!(list.is_empty());
+    assert_eq!(&*value, "a");
+}
+
+#[test]
+fn drops_every_value() {
+    let value = Arc::new(());
+    let list = HarrisList::<_, era::Domain>::new();
+    for key in 0..10 {
+        assert!(list.insert((key, value.clone())));
+    }
+    assert!(!list.insert((0, value.clone())));
+    assert_eq!(Arc::strong_count(&value), 11);
+    for key in 0..5 {
+        assert!(list.remove(&(key, value.clone())));
+    }
+    drop(list);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+stress_reclaimers!(stress, HarrisList);
@@ -0,0 +1,139 @@
+#![cfg(loom)]
+
+use core::{
+    ptr::{self, NonNull},
+    sync::atomic::Ordering,
+};
+
+use atomic_non_null::AtomicNonNull;
+use loom::{
+    sync::{Arc, atomic::AtomicUsize},
+    thread,
+};
+
+const ORDERINGS: [Ordering; 5] = [
+    Ordering::Relaxed,
+    Ordering::Acquire,
+    Ordering::Release,
+    Ordering::AcqRel,
+    Ordering::SeqCst,
+];
+
+const LOAD_ORDERINGS: [Ordering; 3] = [Ordering::Relaxed, Ordering::Acquire, Ordering::SeqCst];
+
+const RELEASE_ORDERINGS: [Ordering; 3] = [Ordering::Release, Ordering::AcqRel, Ordering::SeqCst];
+
+/// A pointer that is only compared, never dereferenced.
+fn addr(addr: usize) -> NonNull<u8> {
+    NonNull::new(ptr::without_provenance_mut(addr)).unwrap()
+}
+
+/// Every swapped-in pointer is either returned by exactly one other swap or left in the cell.
+#[test]
+fn swap() {
+    for order in ORDERINGS {
+        loom::model(move || {
+            let cell = Arc::new(AtomicNonNull::from_non_null(addr(1)));
+            let threads = [2, 3].map(|new| {
+                let cell = cell.clone();
+                thread::spawn(move || cell.swap(addr(new), order).addr().get())
+            });
+            let mut seen = threads.map(|thread| thread.join().unwrap()).to_vec();
+            seen.push(cell.load(Ordering::Relaxed).addr().get());
+            seen.sort_unstable();
+            assert_eq!(seen, [1, 2, 3]);
+        });
+    }
+}
+
+/// A releasing swap publishes the writes made before it to an acquiring load.
+#[test]
+fn swap_publishes() {
+    for order in RELEASE_ORDERINGS {
+        loom::model(move || {
+            let cell = Arc::new(AtomicNonNull::from_non_null(addr(1)));
+            let data = Arc::new(AtomicUsize::new(0));
+            let writer = {
+                let (cell, data) = (cell.clone(), data.clone());
+                thread::spawn(move || {
+                    data.store(1, Ordering::Relaxed);
+                    cell.swap(addr(2), order);
+                })
+            };
+            if cell.load(Ordering::Acquire) == addr(2) {
+                assert_eq!(data.load(Ordering::Relaxed), 1);
+            }
+            writer.join().unwrap();
+        });
+    }
+}
+
+/// Concurrent compare-exchange loops never lose an update.
+#[test]
+fn compare_exchange_weak() {
+    for success in ORDERINGS {
+        for failure in LOAD_ORDERINGS {
+            loom::model(move || {
+                let cell = Arc::new(AtomicNonNull::from_non_null(addr(1)));
+                let threads = [(), ()].map(|()| {
+                    let cell = cell.clone();
+                    thread::spawn(move || {
+                        let mut current = cell.load(Ordering::Relaxed);
+                        loop {
+                            let new = addr(current.addr().get() + 1);
+                            match cell.compare_exchange_weak(current, new, success, failure) {
+                                Ok(prev) => return prev.addr().get(),
+                                Err(actual) => current = actual,
+                            }
+                        }
+                    })
+                });
+                let mut prev = threads.map(|thread| thread.join().unwrap());
+                prev.sort_unstable();
+                assert_eq!(prev, [1, 2]);
+                assert_eq!(cell.load(Ordering::Relaxed), addr(3));
+            });
+        }
+    }
+}
+
+/// Concurrent updates never lose an update, and a rejected update leaves the pointer untouched.
+#[test]
+fn fetch_update() {
+    for set_order in ORDERINGS {
+        for fetch_order in LOAD_ORDERINGS {
+            loom::model(move || {
+                let cell = Arc::new(AtomicNonNull::from_non_null(addr(1)));
+                let increment = {
+                    let cell = cell.clone();
+                    thread::spawn(move || {
+                        cell.fetch_update(set_order, fetch_order, |ptr| {
+                            Some(addr(ptr.addr().get() + 1))
+                        })
+                    })
+                };
+                let reset = {
+                    let cell = cell.clone();
+                    thread::spawn(move || {
+                        cell.fetch_update(set_order, fetch_order, |ptr| {
+                            (ptr == addr(2)).then(|| addr(10))
+                        })
+                    })
+                };
+                assert_eq!(increment.join().unwrap(), Ok(addr(1)));
+                let reset = reset.join().unwrap();
+                let last = cell.load(Ordering::Relaxed);
+                match reset {
+                    Ok(prev) => {
+                        assert_eq!(prev, addr(2));
+                        assert_eq!(last, addr(10));
+                    }
+                    Err(prev) => {
+                        assert_eq!(prev, addr(1));
+                        assert_eq!(last, addr(2));
+                    }
+                }
+            });
+        }
+    }
+}
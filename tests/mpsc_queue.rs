@@ -0,0 +1,136 @@
+#![cfg(all(not(loom), not(feature = "shuttle")))]
+
+use std::{
+    mem::offset_of,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use atomic_non_null::collections::{Adapter, Link, MpscQueue};
+
+const PRODUCERS: usize = 4;
+const PER_PRODUCER: usize = 10_000;
+
+/// A node embedding its link next to the payload, as intrusive users do.
+struct Node {
+    producer: usize,
+    seq: usize,
+    link: Link,
+}
+
+struct NodeAdapter;
+
+// SAFETY: `link` and `from_link` convert between a node and its `link` field by its offset.
+unsafe impl Adapter for NodeAdapter {
+    type Node = Node;
+
+    fn link(node: NonNull<Node>) -> NonNull<Link> {
+        // SAFETY: the offset of a field stays within the node.
+        unsafe { node.byte_add(offset_of!(Node, link)).cast() }
+    }
+
+    unsafe fn from_link(link: NonNull<Link>) -> NonNull<Node> {
+        // SAFETY: the caller guarantees `link` is the `link` field of a node.
+        unsafe { link.byte_sub(offset_of!(Node, link)).cast() }
+    }
+}
+
+fn node(producer: usize, seq: usize) -> NonNull<Node> {
+    NonNull::from(Box::leak(Box::new(Node {
+        producer,
+        seq,
+        link: Link::new(),
+    })))
+}
+
+/// Pops a node on the only consumer and frees it, returning its payload.
+fn pop(queue: &MpscQueue<'_, NodeAdapter>) -> Option<(usize, usize)> {
+    // SAFETY: every test pops from a single thread, and nodes come from `Box::leak`.
+    let node = unsafe { Box::from_raw(queue.pop()?.as_ptr()) };
+    Some((node.producer, node.seq))
+}
+
+#[test]
+fn fifo() {
+    let mut stub = Link::new();
+    let queue = MpscQueue::<NodeAdapter>::new(&mut stub);
+    assert_eq!(pop(&queue), None);
+    for seq in 0..100 {
+        // SAFETY: the node is freed only after it is popped.
+        unsafe { queue.push(node(0, seq)) };
+    }
+    for seq in 0..100 {
+        assert_eq!(pop(&queue), Some((0, seq)));
+    }
+    assert_eq!(pop(&queue), None);
+}
+
+/// Draining the queue down to its last node re-inserts the stub, after which the queue keeps
+/// working, including for a node that was popped before and is pushed again.
+#[test]
+fn reinserts_the_stub() {
+    let mut stub = Link::new();
+    let queue = MpscQueue::<NodeAdapter>::new(&mut stub);
+    let mut reused = Node {
+        producer: 1,
+        seq: 0,
+        link: Link::new(),
+    };
+    let reused = NonNull::from(&mut reused);
+    for round in 0..10 {
+        // SAFETY: the node outlives the queue's use of it and is popped before it is pushed
+        // again.
+        unsafe { queue.push(reused) };
+        // SAFETY: a single thread pops.
+        assert_eq!(unsafe { queue.pop() }, Some(reused));
+        // SAFETY: as above.
+        assert_eq!(unsafe { queue.pop() }, None);
+
+        for seq in 0..round {
+            // SAFETY: the node is freed only after it is popped.
+            unsafe { queue.push(node(0, seq)) };
+        }
+        for seq in 0..round {
+            assert_eq!(pop(&queue), Some((0, seq)));
+        }
+        assert_eq!(pop(&queue), None);
+    }
+}
+
+/// The consumer sees the nodes of every producer in the order they were pushed. `None` is only
+/// returned spuriously while a producer is still pushing, so once every producer finished the
+/// remaining nodes are popped without a gap.
+#[test]
+fn stress() {
+    let mut stub = Link::new();
+    let queue = MpscQueue::<NodeAdapter>::new(&mut stub);
+    let finished = AtomicUsize::new(0);
+    let mut last = [None; PRODUCERS];
+    let mut popped = 0;
+    thread::scope(|s| {
+        for producer in 0..PRODUCERS {
+            let (queue, finished) = (&queue, &finished);
+            s.spawn(move || {
+                for seq in 0..PER_PRODUCER {
+                    // SAFETY: the node is freed only after it is popped.
+                    unsafe { queue.push(node(producer, seq)) };
+                }
+                finished.fetch_add(1, Ordering::Release);
+            });
+        }
+        while popped < PRODUCERS * PER_PRODUCER {
+            let done = finished.load(Ordering::Acquire) == PRODUCERS;
+            let Some((producer, seq)) = pop(&queue) else {
+                assert!(!done, "no node may be missing once every producer finished");
+                thread::yield_now();
+                continue;
+            };
+            assert!(last[producer] < Some(seq));
+            last[producer] = Some(seq);
+            popped += 1;
+        }
+    });
+    assert_eq!(last, [Some(PER_PRODUCER - 1); PRODUCERS]);
+    assert_eq!(pop(&queue), None);
+}
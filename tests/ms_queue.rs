@@ -0,0 +1,90 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{sync::Arc, thread};
+
+use atomic_non_null::{collections::MsQueue, reclaim::Reclaimer};
+
+#[macro_use]
+mod common;
+
+const PRODUCERS: usize = 4;
+const CONSUMERS: usize = 4;
+const PER_PRODUCER: usize = 10_000;
+
+/// Every value is popped exactly once, and each consumer sees the values of every producer in
+/// the order they were pushed, as a linearizable FIFO queue requires.
+fn stress<R: Reclaimer + Send + Sync>(queue: MsQueue<(usize, usize), R>) {
+    let popped = thread::scope(|s| {
+        for producer in 0..PRODUCERS {
+            let queue = &queue;
+            s.spawn(move || {
+                for seq in 0..PER_PRODUCER {
+                    queue.push((producer, seq));
+                }
+            });
+        }
+        let consumers = (0..CONSUMERS)
+            .map(|_| {
+                let queue = &queue;
+                s.spawn(move || {
+                    let mut last = [None; PRODUCERS];
+                    let mut popped = Vec::new();
+                    while popped.len() < PRODUCERS * PER_PRODUCER / CONSUMERS {
+                        assert!(queue.len_estimate() <= PRODUCERS * PER_PRODUCER);
+                        let Some((producer, seq)) = queue.pop() else {
+                            thread::yield_now();
+                            continue;
+                        };
+                        assert!(last[producer] < Some(seq));
+                        last[producer] = Some(seq);
+                        popped.push((producer, seq));
+                    }
+                    popped
+                })
+            })
+            .collect::<Vec<_>>();
+        consumers
+            .into_iter()
+            .flat_map(|consumer| consumer.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    let mut seen = vec![false; PRODUCERS * PER_PRODUCER];
+    for (producer, seq) in popped {
+        assert!(!std::mem::replace(
+            &mut seen[producer * PER_PRODUCER + seq],
+            true
+        ));
+    }
+    assert!(seen.iter().all(|&seen| seen));
+    assert!(queue.is_empty());
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn fifo() {
+    let queue = MsQueue::<usize>::new();
+    assert!(queue.is_empty());
+    for value in 0..100 {
+        queue.push(value);
+    }
+    assert_eq!(queue.len_estimate(), 100);
+    for value in 0..100 {
+        assert_eq!(queue.pop(), Some(value));
+    }
+    assert_eq!(queue.pop(), None);
+}
+
+#[test]
+fn drops_remaining_values() {
+    let value = Arc::new(());
+    let queue = MsQueue::<_>::new();
+    for _ in 0..10 {
+        queue.push(value.clone());
+    }
+    drop(queue.pop());
+    drop(queue);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+stress_reclaimers!(stress, MsQueue);
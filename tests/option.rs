@@ -0,0 +1,80 @@
+#![cfg(all(not(loom), not(feature = "shuttle")))]
+
+use std::{ptr::NonNull, sync::atomic::Ordering};
+
+use atomic_non_null::{AtomicNonNull, AtomicOptionNonNull};
+
+#[test]
+fn take_and_replace() {
+    let mut values = [0u64; 2];
+    let [a, b] = values.each_mut().map(NonNull::from);
+    let ptr = AtomicOptionNonNull::none();
+    assert_eq!(ptr.take(Ordering::Relaxed), None);
+
+    assert_eq!(ptr.replace(a, Ordering::Relaxed), None);
+    assert_eq!(ptr.replace(b, Ordering::Relaxed), Some(a));
+    assert_eq!(ptr.load(Ordering::Relaxed), Some(b));
+    assert_eq!(ptr.take(Ordering::Relaxed), Some(b));
+    assert_eq!(ptr.load(Ordering::Relaxed), None);
+    assert_eq!(ptr.take(Ordering::Relaxed), None);
+}
+
+#[test]
+fn store_swap_and_compare_exchange() {
+    let mut values = [0u64; 2];
+    let [a, b] = values.each_mut().map(NonNull::from);
+    let ptr = AtomicOptionNonNull::new(Some(a));
+    ptr.store(None, Ordering::Relaxed);
+    assert_eq!(ptr.swap(Some(b), Ordering::Relaxed), None);
+
+    assert_eq!(
+        ptr.compare_exchange(None, Some(a), Ordering::SeqCst, Ordering::SeqCst),
+        Err(Some(b))
+    );
+    assert_eq!(
+        ptr.compare_exchange(Some(b), None, Ordering::SeqCst, Ordering::SeqCst),
+        Ok(Some(b))
+    );
+    assert_eq!(
+        ptr.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |ptr| {
+            assert_eq!(ptr, None);
+            Some(Some(a))
+        }),
+        Ok(None)
+    );
+    assert_eq!(ptr.into_inner(), Some(a));
+}
+
+#[test]
+fn conversions() {
+    let mut value = 0u64;
+    let a = NonNull::from(&mut value);
+
+    let ptr = AtomicOptionNonNull::from(AtomicNonNull::from_non_null(a));
+    assert_eq!(ptr.load(Ordering::Relaxed), Some(a));
+    // SAFETY: the pointer is present and nothing stores `None` while the reference is alive.
+    assert_eq!(
+        unsafe { ptr.as_non_null_unchecked() }.load(Ordering::Relaxed),
+        a
+    );
+    assert_eq!(ptr.into_non_null().map(AtomicNonNull::into_inner), Some(a));
+
+    let ptr = AtomicOptionNonNull::from(Some(a));
+    // SAFETY: the pointer is present.
+    assert_eq!(unsafe { ptr.into_non_null_unchecked() }.into_inner(), a);
+
+    assert!(
+        AtomicOptionNonNull::<u64>::from(None)
+            .into_non_null()
+            .is_none()
+    );
+    assert!(
+        AtomicOptionNonNull::<u64>::default()
+            .into_non_null()
+            .is_none()
+    );
+
+    let mut ptr = AtomicOptionNonNull::none();
+    *ptr.get_mut() = Some(a);
+    assert_eq!(ptr.load(Ordering::Relaxed), Some(a));
+}
@@ -0,0 +1,161 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use atomic_non_null::{AtomicNonNull, qsbr::Domain, reclaim::Reclaimer};
+use common::Tracked;
+
+mod common;
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 10_000;
+
+fn tracked(value: usize, drops: &Arc<AtomicUsize>) -> NonNull<Tracked> {
+    NonNull::from(Box::leak(Box::new(Tracked::new(value, drops))))
+}
+
+/// Frees the pointer left in `src` once nothing else can access it.
+fn free(src: AtomicNonNull<Tracked>) {
+    // SAFETY: every pointer stored in `src` comes from `Box::leak`, and the last one is only
+    // owned by `src`.
+    drop(unsafe { Box::from_raw(src.into_inner().as_ptr()) });
+}
+
+#[test]
+fn frees_after_every_handle_is_quiescent() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let (a, b) = (domain.register(), domain.register());
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+
+    // SAFETY: every pointer stored in `src` comes from `Box::leak`.
+    unsafe { a.swap_and_retire(&src, tracked(1, &drops)) };
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    a.quiescent();
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    b.quiescent();
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    drop((a, b));
+    free(src);
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn offline_handles_do_not_hold_back_reclamation() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let (a, b) = (domain.register(), domain.register());
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+
+    b.offline();
+    // SAFETY: every pointer stored in `src` comes from `Box::leak`.
+    unsafe { a.swap_and_retire(&src, tracked(1, &drops)) };
+    a.quiescent();
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    b.online();
+    // SAFETY: as above.
+    unsafe { a.swap_and_retire(&src, tracked(2, &drops)) };
+    a.quiescent();
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+    drop(b);
+    domain.reclaim();
+    assert_eq!(drops.load(Ordering::Relaxed), 2);
+
+    drop(a);
+    free(src);
+    assert_eq!(drops.load(Ordering::Relaxed), 3);
+}
+
+#[test]
+fn dropping_the_domain_frees_retired_nodes() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let handle = domain.register();
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+    for value in 1..10 {
+        // SAFETY: every pointer stored in `src` comes from `Box::leak`.
+        unsafe { handle.swap_and_retire(&src, tracked(value, &drops)) };
+    }
+    assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+    drop(handle);
+    drop(domain);
+    assert_eq!(drops.load(Ordering::Relaxed), 9);
+    free(src);
+}
+
+#[test]
+#[should_panic = "a guard of the handle is still alive"]
+fn quiescent_panics_while_a_guard_is_alive() {
+    let domain = Domain::new();
+    let handle = domain.register();
+    let src = AtomicNonNull::from_non_null(NonNull::from(&0));
+    let _guard = handle.protect(&src);
+    handle.quiescent();
+}
+
+#[test]
+#[should_panic = "the handle is offline"]
+fn protect_panics_while_offline() {
+    let domain = Domain::new();
+    let handle = domain.register();
+    let src = AtomicNonNull::from_non_null(NonNull::from(&0));
+    handle.offline();
+    let _guard = handle.protect(&src);
+}
+
+/// Threads read the current value between quiescent states while others replace it, and every
+/// value is freed exactly once in the end.
+#[test]
+fn stress() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let domain = Domain::new();
+    let src = AtomicNonNull::from_non_null(tracked(0, &drops));
+    thread::scope(|s| {
+        for thread in 0..THREADS {
+            let (domain, src, drops) = (&domain, &src, &drops);
+            s.spawn(move || {
+                let handle = domain.register();
+                for seq in 0..PER_THREAD {
+                    let value = 1 + thread * PER_THREAD + seq;
+                    // SAFETY: the pointer is only freed after this thread is quiescent.
+                    let current = unsafe { src.load(Ordering::Acquire).as_ref() };
+                    assert!(current.check() <= THREADS * PER_THREAD);
+                    if seq % 4 == 0 {
+                        // SAFETY: every pointer stored in `src` comes from `Box::leak`.
+                        unsafe { handle.swap_and_retire(src, tracked(value, drops)) };
+                    }
+                    handle.quiescent();
+                    if seq % 100 == 0 {
+                        handle.offline();
+                        thread::yield_now();
+                        handle.online();
+                    }
+                }
+            });
+        }
+    });
+    drop(domain);
+    free(src);
+    assert_eq!(
+        drops.load(Ordering::Relaxed),
+        1 + THREADS * PER_THREAD.div_ceil(4)
+    );
+}
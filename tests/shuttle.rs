@@ -0,0 +1,151 @@
+#![cfg(feature = "shuttle")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use atomic_non_null::{
+    AtomicArc,
+    collections::{MsQueue, TreiberStack},
+};
+use common::Tracked;
+use shuttle::{sync::Arc, thread};
+
+mod common;
+
+const THREADS: usize = 3;
+const PER_THREAD: usize = 4;
+const ITERATIONS: usize = 1_000;
+
+/// Threads interleave pushes and pops, and every value is popped exactly once in the end.
+fn stack() {
+    let stack = Arc::new(TreiberStack::<usize>::new());
+    let threads = (0..THREADS)
+        .map(|thread| {
+            let stack = stack.clone();
+            thread::spawn(move || {
+                let mut popped = Vec::new();
+                for seq in 0..PER_THREAD {
+                    stack.push(thread * PER_THREAD + seq);
+                    if seq % 2 == 1 {
+                        popped.extend(stack.pop());
+                    }
+                }
+                popped
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut popped = threads
+        .into_iter()
+        .flat_map(|thread| thread.join().unwrap())
+        .collect::<Vec<_>>();
+    popped.extend(stack.pop_all());
+    popped.sort_unstable();
+    assert_eq!(popped, (0..THREADS * PER_THREAD).collect::<Vec<_>>());
+    assert!(stack.is_empty());
+}
+
+/// Every value is popped exactly once, and each consumer sees the values of every producer in
+/// the order they were pushed.
+fn queue() {
+    let queue = Arc::new(MsQueue::<(usize, usize)>::new());
+    for producer in 0..THREADS {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            for seq in 0..PER_THREAD {
+                queue.push((producer, seq));
+            }
+        });
+    }
+    let consumers = (0..THREADS)
+        .map(|_| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                let mut last = [None; THREADS];
+                let mut popped = Vec::new();
+                while popped.len() < PER_THREAD {
+                    let Some((producer, seq)) = queue.pop() else {
+                        thread::yield_now();
+                        continue;
+                    };
+                    assert!(last[producer] < Some(seq));
+                    last[producer] = Some(seq);
+                    popped.push((producer, seq));
+                }
+                popped
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut popped = consumers
+        .into_iter()
+        .flat_map(|consumer| consumer.join().unwrap())
+        .collect::<Vec<_>>();
+    popped.sort_unstable();
+    let expected = (0..THREADS)
+        .flat_map(|producer| (0..PER_THREAD).map(move |seq| (producer, seq)))
+        .collect::<Vec<_>>();
+    assert_eq!(popped, expected);
+    assert!(queue.is_empty());
+}
+
+/// Readers only ever see live values while writers replace them, and every value is dropped
+/// exactly once in the end.
+fn arc() {
+    // The counter is only read once every thread finished, so it need not be modelled.
+    let drops = std::sync::Arc::new(AtomicUsize::new(0));
+    let tracked = |value| std::sync::Arc::new(Tracked::new(value, &drops));
+    let cell = Arc::new(AtomicArc::from(tracked(0)));
+    let threads = (0..THREADS)
+        .map(|thread| {
+            let cell = cell.clone();
+            let values = (0..PER_THREAD)
+                .map(|seq| tracked(1 + thread * PER_THREAD + seq))
+                .collect::<Vec<_>>();
+            thread::spawn(move || {
+                for (seq, value) in values.into_iter().enumerate() {
+                    assert!(cell.load().check() <= THREADS * PER_THREAD);
+                    if seq % 2 == 0 {
+                        cell.store(value);
+                    } else {
+                        let current = cell.load();
+                        let _ = cell.compare_and_swap(&current, value);
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    drop(Arc::into_inner(cell).unwrap());
+    assert_eq!(drops.load(Ordering::Relaxed), 1 + THREADS * PER_THREAD);
+}
+
+/// The cells share the global hazard domain, so both schedulers run in one test rather than in
+/// parallel ones.
+#[test]
+fn arc_random_and_pct() {
+    shuttle::check_random(arc, ITERATIONS);
+    shuttle::check_pct(arc, ITERATIONS, 3);
+}
+
+#[test]
+fn stack_random() {
+    shuttle::check_random(stack, ITERATIONS);
+}
+
+#[test]
+fn stack_pct() {
+    shuttle::check_pct(stack, ITERATIONS, 3);
+}
+
+#[test]
+fn queue_random() {
+    shuttle::check_random(queue, ITERATIONS);
+}
+
+#[test]
+fn queue_pct() {
+    shuttle::check_pct(queue, ITERATIONS, 3);
+}
@@ -0,0 +1,163 @@
+#![cfg(all(not(loom), not(feature = "shuttle")))]
+
+use std::{ptr::NonNull, sync::atomic::Ordering};
+
+use atomic_non_null::AtomicTaggedNonNull;
+
+/// Two values aligned enough for three tag bits.
+fn pointers(values: &mut [u64; 2]) -> [NonNull<u64>; 2] {
+    values.each_mut().map(NonNull::from)
+}
+
+#[test]
+fn compose_and_decompose() {
+    let mut values = [0; 2];
+    let [a, b] = pointers(&mut values);
+    let ptr = AtomicTaggedNonNull::<u64, 3>::new(a, 5);
+    assert_eq!(AtomicTaggedNonNull::<u64, 3>::TAG_MASK, 0b111);
+    assert_eq!(ptr.load(Ordering::Relaxed), (a, 5));
+
+    ptr.store(b, 7, Ordering::Relaxed);
+    assert_eq!(ptr.load(Ordering::Relaxed), (b, 7));
+    assert_eq!(ptr.swap(a, 0, Ordering::Relaxed), (b, 7));
+    assert_eq!(ptr.into_inner(), (a, 0));
+}
+
+#[test]
+fn truncates_tags() {
+    let mut values = [0; 2];
+    let [a, b] = pointers(&mut values);
+    let ptr = AtomicTaggedNonNull::<u64, 2>::new(a, 0b1101);
+    assert_eq!(ptr.load(Ordering::Relaxed), (a, 0b01));
+    ptr.store(b, usize::MAX, Ordering::Relaxed);
+    assert_eq!(ptr.load(Ordering::Relaxed), (b, 0b11));
+    assert_eq!(
+        ptr.fetch_or_tag(usize::MAX << 2, Ordering::Relaxed),
+        (b, 0b11)
+    );
+    assert_eq!(ptr.load(Ordering::Relaxed), (b, 0b11));
+}
+
+/// The pointer and the tag are compared together, so a matching pointer with another tag fails.
+#[test]
+fn compare_exchange_compares_the_pair() {
+    let mut values = [0; 2];
+    let [a, b] = pointers(&mut values);
+    let ptr = AtomicTaggedNonNull::<u64, 3>::new(a, 1);
+    assert_eq!(
+        ptr.compare_exchange((a, 2), (b, 0), Ordering::SeqCst, Ordering::SeqCst),
+        Err((a, 1))
+    );
+    assert_eq!(
+        ptr.compare_exchange((b, 1), (b, 0), Ordering::SeqCst, Ordering::SeqCst),
+        Err((a, 1))
+    );
+    assert_eq!(
+        ptr.compare_exchange((a, 1), (b, 2), Ordering::SeqCst, Ordering::SeqCst),
+        Ok((a, 1))
+    );
+    loop {
+        match ptr.compare_exchange_weak((b, 2), (a, 3), Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(prev) => break assert_eq!(prev, (b, 2)),
+            Err(prev) => assert_eq!(prev, (b, 2)),
+        }
+    }
+    assert_eq!(ptr.load(Ordering::SeqCst), (a, 3));
+}
+
+#[test]
+fn fetch_tag_operations_keep_the_pointer() {
+    let mut value = 0u64;
+    let a = NonNull::from(&mut value);
+    let ptr = AtomicTaggedNonNull::<u64, 3>::new(a, 0b001);
+    assert_eq!(ptr.fetch_or_tag(0b100, Ordering::Relaxed), (a, 0b001));
+    assert_eq!(ptr.load(Ordering::Relaxed), (a, 0b101));
+    assert_eq!(ptr.fetch_and_tag(0b110, Ordering::Relaxed), (a, 0b101));
+    assert_eq!(ptr.load(Ordering::Relaxed), (a, 0b100));
+    assert_eq!(ptr.fetch_and_tag(0, Ordering::Relaxed), (a, 0b100));
+    assert_eq!(ptr.load(Ordering::Relaxed), (a, 0));
+}
+
+#[test]
+#[should_panic = "pointer is not aligned enough to store the tag"]
+fn panics_on_misaligned_pointers() {
+    let mut values = [0u64; 2];
+    let [a, _] = pointers(&mut values);
+    // SAFETY: the address stays within the array.
+    AtomicTaggedNonNull::<u64, 3>::new(unsafe { a.byte_add(4) }, 0);
+}
+
+#[test]
+#[should_panic = "pointer is not aligned enough to store the tag"]
+fn panics_on_misaligned_stores() {
+    let mut values = [0u64; 2];
+    let [a, _] = pointers(&mut values);
+    let ptr = AtomicTaggedNonNull::<u64, 3>::new(a, 0);
+    // SAFETY: the address stays within the array.
+    ptr.store(unsafe { a.byte_add(4) }, 0, Ordering::Relaxed);
+}
+
+#[cfg(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod high {
+    use std::{ptr::NonNull, sync::atomic::Ordering};
+
+    use atomic_non_null::AtomicHighTaggedNonNull;
+
+    type Tagged = AtomicHighTaggedNonNull<u8>;
+
+    #[test]
+    fn compose_and_decompose() {
+        let mut values = [0u8; 2];
+        let [a, b] = values.each_mut().map(NonNull::from);
+        let ptr = Tagged::new(a, Tagged::TAG_MASK);
+        assert_eq!(ptr.load(Ordering::Relaxed), (a, Tagged::TAG_MASK));
+
+        ptr.store(b, 1, Ordering::Relaxed);
+        assert_eq!(ptr.load(Ordering::Relaxed), (b, 1));
+        assert_eq!(ptr.swap(a, 2, Ordering::Relaxed), (b, 1));
+        assert_eq!(ptr.into_inner(), (a, 2));
+    }
+
+    #[test]
+    fn truncates_tags() {
+        let mut value = 0u8;
+        let a = NonNull::from(&mut value);
+        let ptr = Tagged::new(a, Tagged::TAG_MASK + 2);
+        assert_eq!(ptr.load(Ordering::Relaxed), (a, 1));
+        ptr.store(a, usize::MAX, Ordering::Relaxed);
+        assert_eq!(ptr.load(Ordering::Relaxed), (a, Tagged::TAG_MASK));
+    }
+
+    #[test]
+    fn compare_exchange_compares_the_pair() {
+        let mut values = [0u8; 2];
+        let [a, b] = values.each_mut().map(NonNull::from);
+        let ptr = Tagged::new(a, 1);
+        assert_eq!(
+            ptr.compare_exchange((a, 2), (b, 0), Ordering::SeqCst, Ordering::SeqCst),
+            Err((a, 1))
+        );
+        assert_eq!(
+            ptr.compare_exchange((a, 1), (b, 2), Ordering::SeqCst, Ordering::SeqCst),
+            Ok((a, 1))
+        );
+        loop {
+            match ptr.compare_exchange_weak((b, 2), (a, 3), Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(prev) => break assert_eq!(prev, (b, 2)),
+                Err(prev) => assert_eq!(prev, (b, 2)),
+            }
+        }
+        assert_eq!(ptr.load(Ordering::SeqCst), (a, 3));
+    }
+
+    #[test]
+    #[should_panic = "pointer address overlaps the tag bits"]
+    fn panics_on_overlapping_addresses() {
+        let high = NonNull::new(std::ptr::without_provenance_mut::<u8>(1 << 63)).unwrap();
+        Tagged::new(high, 0);
+    }
+}
@@ -0,0 +1,117 @@
+#![cfg(all(feature = "std", not(loom), not(feature = "shuttle")))]
+
+use std::{sync::Arc, thread};
+
+use atomic_non_null::{collections::TreiberStack, era, reclaim::Reclaimer};
+
+#[macro_use]
+mod common;
+
+const PUSHERS: usize = 4;
+const POPPERS: usize = 4;
+const PER_PUSHER: usize = 10_000;
+
+/// Every value is popped exactly once, either by a popper or by the final `pop_all`, and the
+/// length estimate never exceeds the number of values pushed.
+fn stress<R: Reclaimer + Send + Sync>(stack: TreiberStack<usize, R>) {
+    let total = PUSHERS * PER_PUSHER;
+    let mut popped = thread::scope(|s| {
+        for pusher in 0..PUSHERS {
+            let stack = &stack;
+            s.spawn(move || {
+                for seq in 0..PER_PUSHER {
+                    stack.push(pusher * PER_PUSHER + seq);
+                }
+            });
+        }
+        let poppers = (0..POPPERS)
+            .map(|_| {
+                let stack = &stack;
+                s.spawn(move || {
+                    let mut popped = Vec::new();
+                    for _ in 0..total / POPPERS / 2 {
+                        assert!(stack.len_estimate() <= total);
+                        match stack.pop() {
+                            Some(value) => popped.push(value),
+                            None => thread::yield_now(),
+                        }
+                    }
+                    popped
+                })
+            })
+            .collect::<Vec<_>>();
+        poppers
+            .into_iter()
+            .flat_map(|popper| popper.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(stack.len_estimate(), total - popped.len());
+    popped.extend(stack.pop_all());
+    assert_eq!(stack.len_estimate(), 0);
+    assert!(stack.is_empty());
+
+    let mut seen = vec![false; total];
+    for value in popped {
+        assert!(!std::mem::replace(&mut seen[value], true));
+    }
+    assert!(seen.iter().all(|&seen| seen));
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+fn lifo() {
+    let stack = TreiberStack::<usize>::new();
+    assert!(stack.is_empty());
+    for value in 0..100 {
+        stack.push(value);
+    }
+    assert_eq!(stack.len_estimate(), 100);
+    for value in (50..100).rev() {
+        assert_eq!(stack.pop(), Some(value));
+    }
+    assert_eq!(
+        stack.pop_all().collect::<Vec<_>>(),
+        (0..50).rev().collect::<Vec<_>>()
+    );
+    assert_eq!(stack.pop(), None);
+    assert_eq!(stack.len_estimate(), 0);
+
+    stack.push(1);
+    stack.push(2);
+    assert_eq!(stack.into_iter().collect::<Vec<_>>(), [2, 1]);
+}
+
+#[test]
+fn drops_remaining_values() {
+    let value = Arc::new(());
+    let stack = TreiberStack::<_>::new();
+    for _ in 0..10 {
+        stack.push(value.clone());
+    }
+    drop(stack.pop());
+    assert_eq!(Arc::strong_count(&value), 10);
+
+    let mut all = stack.pop_all();
+    drop(all.next());
+    drop(all);
+    assert_eq!(Arc::strong_count(&value), 1);
+    assert_eq!(stack.len_estimate(), 0);
+
+    for _ in 0..10 {
+        stack.push(value.clone());
+    }
+    let mut iter = stack.into_iter();
+    drop(iter.next());
+    drop(iter);
+    assert_eq!(Arc::strong_count(&value), 1);
+
+    let stack = TreiberStack::<_, era::Domain>::new();
+    for _ in 0..10 {
+        stack.push(value.clone());
+    }
+    drop(stack);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+stress_reclaimers!(stress, TreiberStack);
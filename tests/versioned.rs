@@ -0,0 +1,102 @@
+#![cfg(all(not(loom), not(feature = "shuttle")))]
+
+//! Run with `--release` as well, and with `RUSTFLAGS="--cfg atomic_non_null_no_cmpxchg16b"` to
+//! cover the spinlock path on x86_64.
+
+use std::{ptr::NonNull, sync::atomic::Ordering, thread};
+
+use atomic_non_null::AtomicVersionedNonNull;
+
+const THREADS: usize = 4;
+const PER_THREAD: usize = 10_000;
+
+#[test]
+fn updates_bump_the_version() {
+    let (mut a, mut b) = (0, 1);
+    let (a, b) = (NonNull::from(&mut a), NonNull::from(&mut b));
+    let ptr = AtomicVersionedNonNull::new(a);
+    assert_eq!(ptr.load(Ordering::SeqCst), (a, 0));
+
+    ptr.store(b, Ordering::SeqCst);
+    assert_eq!(ptr.load(Ordering::SeqCst), (b, 1));
+    assert_eq!(ptr.swap(a, Ordering::SeqCst), (b, 1));
+    assert_eq!(ptr.load(Ordering::SeqCst), (a, 2));
+    assert_eq!(
+        ptr.compare_exchange((a, 2), a, Ordering::SeqCst, Ordering::SeqCst),
+        Ok((a, 2))
+    );
+    assert_eq!(ptr.into_inner(), (a, 3));
+
+    let ptr = AtomicVersionedNonNull::with_version(a, usize::MAX);
+    ptr.store(b, Ordering::SeqCst);
+    assert_eq!(ptr.load(Ordering::SeqCst), (b, 0));
+}
+
+/// A pair read before the pointer was replaced and stored again is rejected, even though the
+/// pointer matches.
+#[test]
+fn rejects_a_stale_pair() {
+    let (mut a, mut b) = (0, 1);
+    let (a, b) = (NonNull::from(&mut a), NonNull::from(&mut b));
+    let ptr = AtomicVersionedNonNull::new(a);
+    let stale = ptr.load(Ordering::SeqCst);
+    ptr.store(b, Ordering::SeqCst);
+    ptr.store(a, Ordering::SeqCst);
+
+    assert_eq!(
+        ptr.compare_exchange(stale, b, Ordering::SeqCst, Ordering::SeqCst),
+        Err((a, 2))
+    );
+    assert_eq!(
+        ptr.compare_exchange((b, 2), b, Ordering::SeqCst, Ordering::SeqCst),
+        Err((a, 2))
+    );
+    assert_eq!(ptr.load(Ordering::SeqCst), (a, 2));
+}
+
+/// Threads alternate the pointer in compare-exchange loops, and every version is claimed by
+/// exactly one successful exchange.
+#[test]
+fn concurrent_compare_exchange() {
+    let values = [0, 1];
+    let pointers = || values.each_ref().map(NonNull::from);
+    let ptr = AtomicVersionedNonNull::new(pointers()[0]);
+    let mut versions = thread::scope(|s| {
+        let threads = (0..THREADS)
+            .map(|_| {
+                let (ptr, pointers) = (&ptr, &pointers);
+                s.spawn(move || {
+                    let [a, b] = pointers();
+                    let mut versions = Vec::with_capacity(PER_THREAD);
+                    for _ in 0..PER_THREAD {
+                        let mut current = ptr.load(Ordering::Acquire);
+                        loop {
+                            let new = if current.0 == a { b } else { a };
+                            match ptr.compare_exchange(
+                                current,
+                                new,
+                                Ordering::AcqRel,
+                                Ordering::Acquire,
+                            ) {
+                                Ok(prev) => break versions.push(prev.1),
+                                Err(prev) => current = prev,
+                            }
+                        }
+                    }
+                    versions
+                })
+            })
+            .collect::<Vec<_>>();
+        threads
+            .into_iter()
+            .flat_map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    versions.sort_unstable();
+    assert_eq!(versions, (0..THREADS * PER_THREAD).collect::<Vec<_>>());
+    assert_eq!(
+        ptr.load(Ordering::SeqCst),
+        (pointers()[THREADS * PER_THREAD % 2], THREADS * PER_THREAD)
+    );
+}